@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+
+/// Compute units to request for any single-account instruction (the runtime default).
+pub const RECOMMENDED_CU_LIMIT: u32 = 200_000;
+
+/// Per-transaction compute ceiling enforced by the runtime.
+pub const MAX_CU_LIMIT: u32 = 1_400_000;
+
+/// Fixed overhead of a batch call (deserialization, vault update, logging).
+pub const BATCH_BASE_CU: u32 = 20_000;
+
+/// Measured cost of one burn + close pair inside a batch, with headroom.
+pub const BATCH_CU_PER_ACCOUNT: u32 = 15_000;
+
+/// Basis-point denominator (100%).
+pub const MAX_BPS: u16 = 10_000;
+
+/// Seconds an `IdempotencyRecord` must be kept before it can be closed.
+pub const IDEMPOTENCY_RETENTION_SECS: i64 = 86_400;
+
+/// Length of the global daily burn-limit window.
+pub const BURN_WINDOW_SECS: i64 = 86_400;
+
+/// Length of the circuit-breaker window `anomaly_threshold` applies to. Short on
+/// purpose: it's meant to catch a runaway script, not to cap daily volume.
+pub const ANOMALY_WINDOW_SECS: i64 = 300;
+
+/// Namespace prefix for PDA token-account owners handled by `burn_and_close_pda_owned`.
+/// Full seeds are `[ESCROW_SEED, user, ..caller seeds, bump]`.
+pub const ESCROW_SEED: &[u8] = b"escrow";
+
+/// Number of `ShardCounter` PDAs burns can spread their counter writes across.
+pub const NUM_SHARDS: u8 = 16;
+
+/// `BatchResult::status_code` for an account that was burned and closed.
+/// Skipped accounts carry the error code that rejected them instead (6000+).
+pub const BATCH_STATUS_BURNED: u32 = 0;
+
+/// `BatchResult::status_code` for an account that was rescued and closed.
+pub const BATCH_STATUS_RESCUED: u32 = 1;
+
+/// `BatchResult::status_code` for a pair whose token account isn't of the paired
+/// mint – a client ordering bug. Always skipped, even without `allow_partial`.
+pub const BATCH_STATUS_PAIR_MISMATCH: u32 = 2;
+
+/// Most remaining accounts any batch instruction accepts (`batch_burn_and_close`,
+/// `batch_close_vaults`, `fold_shards`, `classify_batch*`), checked before any is
+/// read. Leaves room under the 64 account locks of a transaction for the named
+/// accounts, and keeps a full `batch_burn_and_close` (16 entries) well inside
+/// `MAX_CU_LIMIT`.
+pub const MAX_BATCH_ACCOUNTS: usize = 48;
+
+/// Base fee of one single-signature transaction, no priority fee. `preview_batch_net`
+/// charges it once per `batch_burn_and_close` the accounts would need.
+pub const ESTIMATED_TX_FEE_LAMPORTS: u64 = 5_000;
+
+/// Most `BatchResult`s that fit in return data: 1024 bytes minus the 4-byte
+/// Vec length and `BatchOutcome::next_index`, at 44 bytes per entry. Larger
+/// batches fall back to events.
+pub const MAX_BATCH_RESULTS: usize = 23;
+
+/// Most `VaultStatsView`s `read_vault_stats` returns per call: 1024 bytes of
+/// return data minus the 4-byte Vec length, at 64 bytes per entry. Page larger
+/// leaderboards over several calls.
+pub const MAX_VAULT_STATS: usize = 15;
+
+/// Most `DetailedAccount`s `validate_batch_detailed` returns per call: 1024 bytes
+/// of return data minus the 4-byte Vec length, at 73 bytes per entry.
+pub const MAX_DETAILED_ACCOUNTS: usize = 13;
+
+/// Seed prefix of the stake accounts `compound_vault` opens:
+/// `["vault_stake", vault, stake_count LE]`.
+pub const VAULT_STAKE_SEED: &[u8] = b"vault_stake";
+
+/// Slots in each `ReclaimHistory` ring; once full, every new close overwrites the oldest.
+pub const RECLAIM_HISTORY_LEN: usize = 16;
+
+/// Seed prefix of the per-owner PDA users `approve` for `burn_delegated_capped`:
+/// `["burn_delegate", owner]`.
+pub const BURN_DELEGATE_SEED: &[u8] = b"burn_delegate";
+
+/// Seed prefix of `ScheduledBurn` PDAs: `["scheduled_burn", depositor, mint, schedule_id LE]`.
+pub const SCHEDULED_BURN_SEED: &[u8] = b"scheduled_burn";
+
+/// Seed prefix of the escrow token account a `ScheduledBurn` owns: `["scheduled_escrow", schedule]`.
+pub const SCHEDULED_ESCROW_SEED: &[u8] = b"scheduled_escrow";
+
+/// Seed prefix of `MintBurnLimit` PDAs: ["mint_burn_limit", mint]
+pub const MINT_BURN_LIMIT_SEED: &[u8] = b"mint_burn_limit";
+
+/// How long a proposed vault recovery waits before it can be finalized, giving the
+/// original owner a week to veto it.
+pub const VAULT_RECOVERY_TIMELOCK_SECS: i64 = 7 * 86_400;
+
+/// Mints `burn_and_close_token_account` refuses without `force`: burning these is
+/// never junk cleanup. Wrapped SOL under both token programs; a deployment
+/// protecting more (e.g. its cluster's major stablecoins) appends them here, where
+/// the list stays auditable in one place.
+pub const PROTECTED_MINTS: [Pubkey; 2] = [
+    anchor_spl::token::spl_token::native_mint::ID,
+    anchor_spl::token_2022::spl_token_2022::native_mint::ID,
+];
+
+/// Metaplex Token Metadata program, owner of the metadata account
+/// `burn_and_close_token_account` optionally reads a token name from.
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Most bytes of a Metaplex token name carried in `TokenBurned`; longer names are
+/// cut at a char boundary. Metaplex itself caps names at 32 bytes.
+pub const MAX_EVENT_NAME_LEN: usize = 32;
+
+/// Slots in `BurnerState::token_program_allowlist`, the extra token programs
+/// `burn_close_any_token_program` accepts.
+pub const MAX_ALLOWED_TOKEN_PROGRAMS: usize = 4;
+
+/// SPL Token instruction tags `burn_close_any_token_program` sends; forks keep
+/// the SPL instruction layout, so only the tag and arguments are needed.
+pub const SPL_BURN_TAG: u8 = 8;
+pub const SPL_CLOSE_ACCOUNT_TAG: u8 = 9;
+
+/// `BurnerState::event_level` values, each including everything below it.
+pub const EVENT_LEVEL_NONE: u8 = 0;
+pub const EVENT_LEVEL_ROLLUP: u8 = 1; // one summary event per instruction
+pub const EVENT_LEVEL_FULL: u8 = 2;   // plus one event per account
+
+/// `classify_batch` category bytes, one per remaining account, in order of precedence.
+pub const CLASS_WRONG_OWNER: u8 = 0; // not a token account of `user` – skip it
+pub const CLASS_FROZEN: u8 = 1;      // needs a thaw before anything else
+pub const CLASS_BURNABLE: u8 = 2;    // has a balance, burn + close
+pub const CLASS_EMPTY: u8 = 3;       // zero balance, plain close
+
+/// Indices into `BurnerState::roles`. Each role may run its admin actions in
+/// addition to the main authority.
+pub const ROLE_LIMITS: u8 = 0; // set_daily_burn_limit, set_max_supply_pct_bps, set_dust_threshold, set_min_withdraw, set_withdraw_cooldown, set_anomaly_threshold, set_min_batch_count, set_mint_daily_cap
+pub const ROLE_FEES: u8 = 1;   // set_rent_split, migrate_treasury, set_mint_fee, clear_mint_fee, set_token_fee, set_fee_waiver
+pub const ROLE_OPS: u8 = 2;    // set_event_level, admin_close_empty, backfill_vault_stats, set_vault_creation_paused, set_paused, set_duplicate_close_check, propose_vault_recovery
+pub const NUM_ROLES: usize = 3;
+
+/// Layout version of `BurnerState`, written by `initialize` and `migrate_state_bump`;
+/// bump with each schema change. Every version only appends to the one before:
+/// * 1 – `version` itself, after `state_bump`
+/// * 2 – `withdraw_cooldown_secs`
+/// * 3 – `vault_creation_paused`
+/// * 4 – `stats_resettable`, `stats_reset_locked`
+/// * 5 – `token_fee_amount`, `token_fee_mint`, `token_fee_treasury`
+/// * 6 – `is_paused` and the circuit breaker's `anomaly_*` / `burned_in_anomaly_window`
+/// * 7 – `min_batch_count`
+/// * 8 – `check_duplicate_closes`
+/// * 9 – `token_program_allowlist`
+/// * 10 – `total_rent_reclaimed`, `last_active_at`
+/// * 11 – `fee_waiver_until`
+/// * 12 – `closed_vault_tokens_burned`, `closed_vault_accounts_closed`
+pub const STATE_VERSION: u8 = 12;
+
+/// The crate version as `[major, minor, patch]`, parsed at compile time so a
+/// malformed `CARGO_PKG_VERSION` fails the build rather than the call.
+pub const PROGRAM_VERSION: [u16; 3] = parse_semver(env!("CARGO_PKG_VERSION"));
+
+// Parses the `MAJOR.MINOR.PATCH` core of a semver string; any `-pre` or `+build`
+// suffix is ignored, since it has no stable numeric form.
+const fn parse_semver(version: &str) -> [u16; 3] {
+    let bytes = version.as_bytes();
+    let mut parts = [0u16; 3];
+    let mut part = 0;
+    let mut digits = 0;
+    let mut i = 0;
+    while i < bytes.len() && bytes[i] != b'-' && bytes[i] != b'+' {
+        let b = bytes[i];
+        if b == b'.' {
+            assert!(digits > 0 && part < 2, "CARGO_PKG_VERSION is not MAJOR.MINOR.PATCH");
+            part += 1;
+            digits = 0;
+        } else {
+            assert!(b.is_ascii_digit(), "CARGO_PKG_VERSION is not MAJOR.MINOR.PATCH");
+            parts[part] = parts[part] * 10 + (b - b'0') as u16;
+            digits += 1;
+        }
+        i += 1;
+    }
+    assert!(digits > 0 && part == 2, "CARGO_PKG_VERSION is not MAJOR.MINOR.PATCH");
+    parts
+}
+
+/// `supported_features` bits. Built-in capabilities of this program version:
+pub const FEATURE_TOKEN_2022: u64 = 1 << 0;       // Token-2022 accounts in close/burn
+pub const FEATURE_RELAY: u64 = 1 << 1;            // relayed_burn_and_close
+pub const FEATURE_BATCH_RESCUE: u64 = 1 << 2;     // rescue mode in batch_burn_and_close
+pub const FEATURE_EPOCH_STATS: u64 = 1 << 3;      // EpochStats rollups
+pub const FEATURE_SCHEDULED_BURN: u64 = 1 << 4;   // schedule_burn / execute_scheduled_burn
+pub const FEATURE_VAULT_RECOVERY: u64 = 1 << 5;   // propose / veto / finalize_vault_recovery
+/// Compiled in for this deployment:
+pub const FEATURE_COMPACT_LOGS: u64 = 1 << 16;    // `compact-logs` packed logging
+pub const FEATURE_TEST_HELPERS: u64 = 1 << 17;    // `test-helpers` mock clock etc.
+pub const FEATURE_EVENT_CPI: u64 = 1 << 18;       // `event-cpi` self-CPI events on burn / unwrap
+/// Switched on by the current `BurnerState` config:
+pub const FEATURE_FEES: u64 = 1 << 32;            // a referral or treasury rent split
+pub const FEATURE_DAILY_LIMIT: u64 = 1 << 33;     // daily_burn_limit > 0
+pub const FEATURE_SUPPLY_GUARDRAIL: u64 = 1 << 34; // max_supply_pct_bps > 0
+pub const FEATURE_DUST_CLOSE: u64 = 1 << 35;      // dust_threshold > 0
+pub const FEATURE_MIN_WITHDRAW: u64 = 1 << 36;    // min_withdraw_lamports > 0
+pub const FEATURE_EVENTS: u64 = 1 << 37;          // event_level above EVENT_LEVEL_NONE
+pub const FEATURE_WITHDRAW_COOLDOWN: u64 = 1 << 38; // withdraw_cooldown_secs > 0
+pub const FEATURE_TOKEN_FEE: u64 = 1 << 39;       // token_fee_amount > 0
+pub const FEATURE_CIRCUIT_BREAKER: u64 = 1 << 40; // anomaly_threshold > 0
+pub const FEATURE_FEE_WAIVER: u64 = 1 << 41;      // fee_waiver_until still ahead
+
+/// First byte of each `compact-logs` record; field layouts are documented in `logs`.
+pub const LOG_TAG_CLOSED: u8 = 1;
+pub const LOG_TAG_BURNED_CLOSED: u8 = 2;
+pub const LOG_TAG_BATCH_SKIPPED: u8 = 3;
+pub const LOG_TAG_BATCH_RESCUED: u8 = 4;
+pub const LOG_TAG_BATCH_SUMMARY: u8 = 5;
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum BurnerError {
+    #[msg("Invalid owner")] // thrown when caller != vault.owner
+    InvalidOwner,
+    
+    #[msg("Token account not owned by user")] // thrown when token account owner != signer
+    UnauthorizedAccount,
+    
+    #[msg("Token account is not empty")] // thrown when trying to close non-empty account
+    AccountNotEmpty,
+
+    #[msg("Account is not a valid SPL token account")] // thrown when a raw account fails token deserialization
+    NotATokenAccount,
+
+    #[msg("Idempotency record is still within its retention window")] // thrown when closing a fresh record
+    IdempotencyRecordTooRecent,
+
+    #[msg("Basis points out of range")] // thrown when bps > MAX_BPS, and by burn_bps for 0
+    InvalidBps,
+
+    #[msg("Arithmetic overflow")] // thrown when checked math fails
+    MathOverflow,
+
+    #[msg("Batch accounts must be non-empty (token account, mint) pairs")] // thrown on malformed remaining_accounts
+    InvalidBatchAccounts,
+
+    #[msg("Token account mint does not match the provided mint")] // thrown when a batch pair is misaligned
+    MintMismatch,
+
+    #[msg("Token account is frozen")] // thrown when a frozen account can't be burned or closed
+    AccountFrozen,
+
+    #[msg("Signer is not the program authority")] // thrown when an admin ix is signed by someone else
+    UnauthorizedAuthority,
+
+    #[msg("Mint authority is not the state PDA")] // thrown when the receipt mint isn't controlled by the program
+    InvalidMintAuthority,
+
+    #[msg("Burn would exceed the global daily burn limit")] // thrown when burned_in_window would pass daily_burn_limit
+    DailyLimitExceeded,
+
+    #[msg("Seeds do not derive the token account owner")] // thrown when a PDA-owned burn gets the wrong seeds or bump
+    InvalidPdaSeeds,
+
+    #[msg("Vault stats were already backfilled")] // thrown when backfill_vault_stats runs twice on a vault
+    StatsAlreadyBackfilled,
+
+    #[msg("Rescue account must be a token account of the same mint owned by the rescue owner")] // thrown on a bad rescue triple
+    InvalidRescueAccount,
+
+    #[msg("Shard index must be below NUM_SHARDS")] // thrown when init_shard gets an out-of-range index
+    InvalidShardIndex,
+
+    #[msg("Account is not a shard counter PDA")] // thrown when fold_shards gets a foreign account
+    InvalidShardAccount,
+
+    #[msg("Burn exceeds the configured share of mint supply")] // thrown when a non-forced burn passes max_supply_pct_bps
+    SupplyGuardrailExceeded,
+
+    #[msg("Vault requires its cosigner's signature")] // thrown when a cosigned vault action lacks the cosigner
+    MissingCosigner,
+
+    #[msg("Token account close authority is not the state PDA")] // thrown when admin_close_empty lacks the owner's opt-in
+    InvalidCloseAuthority,
+
+    #[msg("Event level must be 0 (none), 1 (rollup) or 2 (full)")] // thrown when set_event_level gets an unknown level
+    InvalidEventLevel,
+
+    #[msg("Signer is not the mint's permanent delegate")] // thrown when the mint lacks the extension or names someone else
+    NotPermanentDelegate,
+
+    #[msg("Referrer cannot be the user")] // thrown when burn_close_refer gets the user's own vault as referrer
+    SelfReferral,
+
+    #[msg("Payer is not the vault's authorized operator")] // thrown when a relayer claims a fee without the user's approval
+    UnauthorizedOperator,
+
+    #[msg("Operator fee exceeds the rent reclaimed")] // thrown when operator_fee_lamports is above the closed account's rent
+    OperatorFeeTooHigh,
+
+    #[msg("Vault does not belong to the token account owner")] // thrown when a close's rent destination vault isn't the token account owner's
+    VaultOwnerMismatch,
+
+    #[msg("Unknown role")] // thrown when set_role gets an index at or above NUM_ROLES
+    InvalidRole,
+
+    #[msg("Burn amount exceeds the mint's supply")] // thrown when simulate_burn_impact is asked about more tokens than exist
+    BurnExceedsSupply,
+
+    #[msg("Account is not a canonical vault PDA")] // thrown when batch_close_vaults gets a non-vault or non-canonical vault account
+    InvalidVaultAccount,
+
+    #[msg("Mint account required to burn dust before closing")] // thrown when close_token_account finds dust but no mint was passed
+    MintRequiredForDust,
+
+    #[msg("Withdrawable amount is below the configured minimum")] // thrown when withdraw_vault would move less than min_withdraw_lamports
+    BelowMinWithdraw,
+
+    #[msg("Instruction re-entered while a burn was in progress")] // thrown when begin_processing finds the guard already set
+    ReentrantCall,
+
+    #[msg("Withdrawal would leave the vault below its rent floor")] // thrown when withdraw_vault_amount asks for more than the usable lamports
+    WouldBeUnderRentExempt,
+
+    #[msg("Transaction executed after its deadline")] // thrown when burn_and_close_token_account runs past a non-zero deadline
+    DeadlineExceeded,
+
+    #[msg("Signer is not the mint's freeze authority")] // thrown when issuer_retire_account is signed by anyone else
+    NotFreezeAuthority,
+
+    #[msg("Too many vaults for one read_vault_stats call")] // thrown above MAX_VAULT_STATS remaining accounts
+    TooManyVaultStats,
+
+    #[msg("Token account's close authority is set to someone other than the signer")] // thrown before a close the token program would refuse
+    CloseAuthorityConflict,
+
+    #[msg("Vault has not opted into compounding")] // thrown by compound_vault while compound_threshold is 0
+    CompoundingDisabled,
+
+    #[msg("Vault balance is below its compound threshold or the stake minimum")] // thrown when compound_vault has too little to stake
+    CompoundBelowThreshold,
+
+    #[msg("Too many remaining accounts for one batch")] // thrown above MAX_BATCH_ACCOUNTS
+    BatchTooLarge,
+
+    #[msg("Token account is not a wrapped SOL account")] // thrown when unwrap_and_close_wsol gets another mint
+    NotWrappedSol,
+
+    #[msg("Cannot close one of the program's own accounts")] // thrown when token_account is the vault, state or treasury
+    CannotCloseProgramAccount,
+
+    #[msg("New treasury is the current treasury")] // thrown by migrate_treasury when nothing would move
+    TreasuryUnchanged,
+
+    #[msg("Mint has an active freeze authority")] // thrown in strict mode unless the burn is forced
+    FreezableMint,
+
+    #[msg("Withdrawal cooldown has not elapsed")] // thrown by withdraw_vault* inside withdraw_cooldown_secs of the last one
+    WithdrawCooldownActive,
+
+    #[msg("Vault creation is paused")] // thrown when a new vault would be created while vault_creation_paused is set
+    VaultCreationPaused,
+
+    #[msg("Burn delegate PDA is not the account's approved delegate")] // thrown by burn_delegated_capped before approve or once the allowance is used up
+    BurnDelegateNotApproved,
+
+    #[msg("Burn exceeds the remaining delegated amount")] // thrown by burn_delegated_capped above delegated_amount
+    DelegationExceeded,
+
+    #[msg("Rent destination must be the owner's vault or wallet")] // thrown by close_token_account_to for any other account
+    InvalidRentDestination,
+
+    #[msg("Stats reset is not enabled")] // thrown by reset_stats while stats_resettable is false
+    StatsNotResettable,
+
+    #[msg("Stats reset has been locked off")] // thrown by set_stats_resettable after lock_stats_reset
+    StatsResetLocked,
+
+    #[msg("Metadata account is not the Metaplex metadata of this mint")] // thrown by burn_and_close_token_account for a foreign or malformed metadata account
+    InvalidMetadataAccount,
+
+    #[msg("Token fee accounts are required while a token fee is set")] // thrown by burn_and_close_token_account when a fee account is missing
+    TokenFeeAccountsMissing,
+
+    #[msg("Token fee account does not match the configured fee")] // thrown by set_token_fee and burn_and_close_token_account for a mismatched fee account
+    InvalidTokenFeeAccount,
+
+    #[msg("Burns are paused")] // thrown by every burn while is_paused is set, manually or by the circuit breaker
+    ProgramPaused,
+
+    #[msg("Account is frozen by the mint's DefaultAccountState; the freeze authority must co-sign to thaw it")] // thrown by burn_and_close_token_account for a default-frozen account without freeze_authority
+    DefaultFrozenAccount,
+
+    #[msg("Account is frozen and its mint has no freeze authority, so it can never be thawed")] // thrown by burn_and_close_token_account when thawing is impossible
+    FrozenWithoutFreezeAuthority,
+
+    #[msg("Batch has fewer token accounts than the configured minimum")] // thrown by batch_burn_and_close below min_batch_count
+    BatchTooSmall,
+
+    #[msg("Token account is closed by another instruction of this transaction")] // thrown while check_duplicate_closes is on
+    DuplicateInTransaction,
+
+    #[msg("Instructions sysvar is required while the duplicate close check is on")] // thrown when the optional instructions account is missing
+    InstructionsSysvarMissing,
+
+    #[msg("Token program is not on the allowlist")] // thrown by burn_close_any_token_program and remove_token_program
+    TokenProgramNotAllowed,
+
+    #[msg("Token program allowlist is full")] // thrown by add_token_program once all MAX_ALLOWED_TOKEN_PROGRAMS slots are used
+    TokenProgramAllowlistFull,
+
+    #[msg("Unlock time must be in the future")] // thrown by schedule_burn for an unlock_at at or before now
+    InvalidUnlockTime,
+
+    #[msg("Scheduled burn is not unlocked yet")] // thrown by execute_scheduled_burn before unlock_at
+    BurnNotUnlocked,
+
+    #[msg("Scheduled burn amount must be greater than zero")] // thrown by schedule_burn for a zero amount
+    ZeroScheduledAmount,
+
+    #[msg("Scheduled burn is unlocked and can no longer be cancelled")] // thrown by cancel_scheduled_burn from unlock_at on
+    BurnCommitted,
+
+    #[msg("Token-2022 account in a batch without the Token-2022 program")] // thrown by batch_burn_and_close, skippable with allow_partial
+    Token2022ProgramMissing,
+
+    #[msg("Mint is protected from burning; pass force to override")] // thrown by burn_and_close_token_account for a PROTECTED_MINTS mint
+    ProtectedSystemMint,
+
+    #[msg("No matching vault recovery is pending")] // thrown by veto_recovery, finalize_vault_recovery
+    NoRecoveryPending,
+
+    #[msg("Vault recovery timelock has not elapsed")] // thrown by finalize_vault_recovery
+    RecoveryTimelockActive,
+
+    #[msg("DAO handover must be confirmed")] // thrown by handover_to_dao without confirm
+    HandoverNotConfirmed,
+
+    #[msg("DAO authority is not a PDA of the DAO program")] // thrown by handover_to_dao when dao_seeds don't derive dao_authority
+    InvalidDaoAuthority,
+
+    #[msg("Burn would exceed this mint's daily cap")] // thrown by burns of a mint with a MintBurnLimit
+    MintDailyCapExceeded,
+
+    #[msg("Account is not the MintBurnLimit PDA of the mint")] // thrown by batch_burn_and_close for a mismatched limit account
+    InvalidMintBurnLimit,
+}
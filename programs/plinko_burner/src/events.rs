@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+// Emitted by `batch_burn_and_close` when a token account goes down the burn path (EVENT_LEVEL_FULL)
+#[event]
+pub struct TokenAccountBurned {
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,          // raw tokens burned
+}
+
+// Emitted by `burn_and_close_token_account` (EVENT_LEVEL_FULL)
+#[event]
+pub struct TokenBurned {
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,          // raw tokens burned
+    pub rent: u64,            // lamports sent to the vault
+    pub name: String,         // Metaplex name, at most MAX_EVENT_NAME_LEN bytes; empty without metadata
+}
+
+// Emitted by `batch_burn_and_close` when a token account goes down the rescue path (EVENT_LEVEL_FULL)
+#[event]
+pub struct TokenAccountRescued {
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,          // raw tokens moved instead of burned
+    pub rescue_account: Pubkey,
+}
+
+// Emitted by `validate_token_account` so UIs can format balances without a mint fetch
+#[event]
+pub struct ValidationReport {
+    pub mint: Pubkey,
+    pub amount: u64,          // raw balance
+    pub decimals: Option<u8>, // None when the mint account wasn't passed
+    pub is_empty: bool,
+    pub immutable_owner: bool, // Token-2022 ImmutableOwner extension present; always false for legacy accounts
+}
+
+// Emitted once by `batch_burn_and_close` at EVENT_LEVEL_ROLLUP and above
+#[event]
+pub struct BatchCompleted {
+    pub user: Pubkey,
+    pub processed: u32,       // burned + rescued entries
+    pub skipped: u32,
+    pub burned: u64,          // raw tokens burned across the batch
+    pub rent: u64,            // lamports sent to the vault
+}
+
+// Emitted per entry by `batch_burn_and_close` when the batch has more than
+// `MAX_BATCH_RESULTS` entries and the results don't fit in return data (EVENT_LEVEL_FULL)
+#[event]
+pub struct BatchAccountResult {
+    pub pubkey: Pubkey,
+    pub status_code: u32,
+    pub rent: u64,
+}
+
+// Emitted by `burn_close_refer` at EVENT_LEVEL_ROLLUP and above
+#[event]
+pub struct RentSplit {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub token_account: Pubkey,
+    pub user_share: u64,      // lamports kept in the user's vault
+    pub referral_share: u64,  // lamports moved to the referrer's vault
+    pub treasury_share: u64,  // lamports moved to the treasury
+}
+
+// Emitted by `relayed_burn_and_close` whenever an operator fee is paid (not gated by event_level)
+#[event]
+pub struct OperatorFeePaid {
+    pub user: Pubkey,
+    pub operator: Pubkey,
+    pub fee: u64,             // lamports moved from the reclaimed rent to the operator
+    pub rent: u64,            // rent reclaimed by the close
+}
+
+// Emitted by `relayed_burn_and_close` when it created the user's vault (not gated by event_level)
+#[event]
+pub struct VaultRentReimbursed {
+    pub user: Pubkey,
+    pub payer: Pubkey,        // relayer that fronted the vault rent
+    pub reimbursement: u64,   // lamports paid back from the reclaimed rent
+    pub creation_rent: u64,   // what creating the vault cost, the reimbursement cap
+}
+
+// Emitted by `unwrap_and_close_wsol`; both amounts went to the user's wallet
+#[event]
+pub struct WsolUnwrapped {
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub lamports_unwrapped: u64, // wrapped principal, above the rent reserve
+    pub rent_lamports: u64,      // the account's rent-exempt reserve
+}
+
+// Emitted by `migrate_treasury` (not gated by event_level)
+#[event]
+pub struct TreasuryMigrated {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub lamports: u64,        // moved from the old treasury to the new one
+    pub closed: bool,         // the old treasury was drained to zero
+}
+
+// Emitted by `handover_to_dao` (not gated by event_level)
+#[event]
+pub struct DaoHandover {
+    pub old_authority: Pubkey,
+    pub dao_program: Pubkey,
+    pub dao_authority: Pubkey, // new authority and treasury
+}
+
+// Emitted by the burn that trips the circuit breaker (not gated by event_level)
+#[event]
+pub struct CircuitBreakerTripped {
+    pub burned_in_window: u128, // raw tokens burned in the window, this burn included
+    pub threshold: u64,         // anomaly_threshold at the time
+    pub window_start: i64,      // Unix timestamp the window opened
+}
+
+// Emitted by `propose_vault_recovery` (not gated by event_level)
+#[event]
+pub struct VaultRecoveryProposed {
+    pub vault: Pubkey,
+    pub owner: Pubkey,      // current owner, who can veto until unlock_at
+    pub new_owner: Pubkey,
+    pub unlock_at: i64,
+    pub authority: Pubkey,  // admin that proposed it
+}
+
+// Emitted by `veto_recovery` (not gated by event_level)
+#[event]
+pub struct VaultRecoveryVetoed {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,  // the proposal that was cancelled
+}
+
+// Emitted by `finalize_vault_recovery` (not gated by event_level)
+#[event]
+pub struct VaultRecoveryFinalized {
+    pub vault: Pubkey,      // recovered vault, closed by now
+    pub owner: Pubkey,      // previous owner
+    pub new_owner: Pubkey,
+    pub new_vault: Pubkey,  // received the lamports and the lamports_collected tally
+    pub lamports: u64,
+}
+
+// Emitted by `schedule_burn`, the public record of the commitment (not gated by event_level)
+#[event]
+pub struct BurnScheduled {
+    pub depositor: Pubkey,
+    pub mint: Pubkey,
+    pub schedule: Pubkey,  // ScheduledBurn PDA, and the escrow's authority
+    pub amount: u64,       // raw tokens escrowed
+    pub unlock_at: i64,
+}
+
+// Emitted by `execute_scheduled_burn` (not gated by event_level)
+#[event]
+pub struct ScheduledBurnExecuted {
+    pub depositor: Pubkey,
+    pub mint: Pubkey,
+    pub schedule: Pubkey,
+    pub amount: u64,       // raw tokens burned, anything sent to the escrow since included
+    pub executor: Pubkey,  // whoever sent the transaction
+}
+
+// Emitted by `cancel_scheduled_burn` (not gated by event_level)
+#[event]
+pub struct ScheduledBurnCancelled {
+    pub depositor: Pubkey,
+    pub mint: Pubkey,
+    pub schedule: Pubkey,
+    pub amount: u64,       // raw tokens returned to the depositor
+}
+
+// Emitted by `reset_stats` (not gated by event_level)
+#[event]
+pub struct StatsReset {
+    pub authority: Pubkey,
+    pub vault: Option<Pubkey>, // owner of the vault reset alongside, if one was passed
+}
@@ -0,0 +1,1388 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{Token, SetAuthority, set_authority};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::constants::{
+    ANOMALY_WINDOW_SECS, EVENT_LEVEL_FULL, EVENT_LEVEL_NONE, FEATURE_BATCH_RESCUE, FEATURE_CIRCUIT_BREAKER, FEATURE_COMPACT_LOGS, FEATURE_DAILY_LIMIT,
+    FEATURE_DUST_CLOSE, FEATURE_EPOCH_STATS, FEATURE_EVENT_CPI, FEATURE_EVENTS, FEATURE_FEES, FEATURE_FEE_WAIVER, FEATURE_MIN_WITHDRAW, FEATURE_RELAY, FEATURE_SCHEDULED_BURN, FEATURE_VAULT_RECOVERY,
+    FEATURE_SUPPLY_GUARDRAIL, FEATURE_TEST_HELPERS, FEATURE_TOKEN_2022, FEATURE_TOKEN_FEE, FEATURE_WITHDRAW_COOLDOWN, MAX_ALLOWED_TOKEN_PROGRAMS, MAX_BATCH_ACCOUNTS, MAX_BPS, MINT_BURN_LIMIT_SEED, NUM_ROLES, PROGRAM_VERSION,
+    ROLE_FEES, ROLE_LIMITS, ROLE_OPS, STATE_VERSION,
+};
+use crate::errors::BurnerError;
+use crate::events::{DaoHandover, StatsReset, TreasuryMigrated};
+use crate::instructions::close::record_reclaimed_rent;
+use crate::state::{BurnerState, MintBurnLimit, MintFeeOverride, VaultAccount};
+
+/// Layout version of `ConfigView`; bump whenever fields are added or reordered.
+pub const CONFIG_VIEW_VERSION: u8 = 2;
+
+// Return-data payload of `get_config` – stable view over `BurnerState`.
+// Version 2 appended referral_bps, treasury_bps and is_paused.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfigView {
+    pub version: u8,         // CONFIG_VIEW_VERSION at the time of the call
+    pub authority: Pubkey,
+    pub is_initialized: bool,
+    pub created_at: i64,
+    pub referral_bps: u16,   // rent split of burn_close_refer
+    pub treasury_bps: u16,
+    pub is_paused: bool,     // burns refused, by set_paused or the circuit breaker
+}
+
+/// Layout version of `DashboardView`; bump whenever fields are added or reordered.
+pub const DASHBOARD_VIEW_VERSION: u8 = 1;
+
+// Return-data payload of `global_dashboard` – the headline stats of a public stats
+// page. Borsh, 94 bytes in version 1, at these offsets:
+//   0 version u8 | 1 authority [u8; 32] | 33 total_burned u128 | 49 accounts_closed u64
+//   57 total_rent_reclaimed u128 | 73 referral_bps u16 | 75 treasury_bps u16
+//   77 is_paused bool | 78 last_active_at i64 | 86 last_folded_at i64
+// New fields only ever go at the end, with a version bump.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DashboardView {
+    pub version: u8,               // DASHBOARD_VIEW_VERSION at the time of the call
+    pub authority: Pubkey,
+    pub total_burned: u128,        // the three totals are as of last_folded_at
+    pub accounts_closed: u64,
+    pub total_rent_reclaimed: u128,
+    pub referral_bps: u16,         // rent split of burn_close_refer
+    pub treasury_bps: u16,
+    pub is_paused: bool,           // burns refused, by set_paused or the circuit breaker
+    pub last_active_at: i64,       // last burn on any path, 0 = none yet
+    pub last_folded_at: i64,       // 0 = never folded
+}
+
+// Return-data payload of `program_version`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProgramVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub state_version: u8,   // `BurnerState.version` of the live state account
+}
+
+pub(crate) fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    let state = &mut ctx.accounts.state;           // mutable alias to PDA
+    let clock = Clock::get()?;                     // current cluster time
+
+    // Populate persistent fields
+    state.authority      = ctx.accounts.authority.key(); //admin address
+    state.is_initialized = true;                   // sanity flag
+    state.created_at     = clock.unix_timestamp;   // cluster time
+    state.daily_burn_limit = 0;                    // unlimited until configured
+    state.window_start     = clock.unix_timestamp; // first burn window opens now
+    state.burned_in_window = 0;
+    state.total_burned     = 0;                    // grows via fold_shards
+    state.accounts_closed  = 0;
+    state.last_folded_at   = 0;
+    state.max_supply_pct_bps = 0;                  // guardrail off by default
+    state.event_level      = EVENT_LEVEL_FULL;     // everything, until tuned down
+    state.referral_bps     = 0;                    // rent split off until configured
+    state.treasury_bps     = 0;
+    state.treasury         = state.authority;      // a valid, writable default destination
+    state.mock_unix_timestamp = 0;                 // real clock
+    state.mock_epoch       = 0;
+    state.roles            = [Pubkey::default(); NUM_ROLES]; // everything on the main authority
+    state.closed_vault_lamports = 0;
+    state.dust_threshold   = 0;                    // only truly empty accounts close
+    state.min_withdraw_lamports = 0;               // withdrawals of any size
+    state.processing       = false;
+    state.state_bump       = ctx.bumps.state;      // later contexts check against this, no re-derive
+    state.version          = STATE_VERSION;
+    state.withdraw_cooldown_secs = 0;              // withdraw as often as wanted
+    state.vault_creation_paused = false;
+    state.stats_resettable = false;                // reset_stats is opt-in
+    state.stats_reset_locked = false;
+    state.token_fee_amount = 0;                    // no token fee until set_token_fee
+    state.token_fee_mint   = Pubkey::default();
+    state.token_fee_treasury = Pubkey::default();
+    state.is_paused        = false;
+    state.anomaly_threshold = 0;                   // circuit breaker off
+    state.anomaly_window_start = clock.unix_timestamp;
+    state.burned_in_anomaly_window = 0;
+    state.min_batch_count  = 0;                    // single-entry batches allowed
+    state.check_duplicate_closes = false;          // no instructions-sysvar scan
+    state.token_program_allowlist = [Pubkey::default(); MAX_ALLOWED_TOKEN_PROGRAMS];
+    state.total_rent_reclaimed = 0;                // grows via fold_shards
+    state.last_active_at   = 0;                    // no burn yet
+    state.fee_waiver_until = 0;                    // fees apply as configured
+    state.closed_vault_tokens_burned = 0;
+    state.closed_vault_accounts_closed = 0;
+
+    msg!("Token Burner initialized with authority: {}", state.authority);
+    Ok(())
+}
+
+pub(crate) fn get_config(ctx: Context<GetConfig>) -> Result<ConfigView> {
+    let state = &ctx.accounts.state;
+
+    Ok(ConfigView {
+        version:        CONFIG_VIEW_VERSION,
+        authority:      state.authority,
+        is_initialized: state.is_initialized,
+        created_at:     state.created_at,
+        referral_bps:   state.referral_bps,
+        treasury_bps:   state.treasury_bps,
+        is_paused:      state.is_paused,
+    })
+}
+
+pub(crate) fn global_dashboard(ctx: Context<GlobalDashboard>) -> Result<DashboardView> {
+    let state = &ctx.accounts.state;
+
+    Ok(DashboardView {
+        version:              DASHBOARD_VIEW_VERSION,
+        authority:            state.authority,
+        total_burned:         state.total_burned,
+        accounts_closed:      state.accounts_closed,
+        total_rent_reclaimed: state.total_rent_reclaimed,
+        referral_bps:         state.referral_bps,
+        treasury_bps:         state.treasury_bps,
+        is_paused:            state.is_paused,
+        last_active_at:       state.last_active_at,
+        last_folded_at:       state.last_folded_at,
+    })
+}
+
+pub(crate) fn program_version(ctx: Context<ProgramVersionView>) -> Result<ProgramVersion> {
+    let [major, minor, patch] = PROGRAM_VERSION;
+
+    Ok(ProgramVersion {
+        major,
+        minor,
+        patch,
+        state_version: ctx.accounts.state.version,
+    })
+}
+
+pub(crate) fn supported_features(ctx: Context<SupportedFeatures>) -> Result<u64> {
+    let state = &ctx.accounts.state;
+
+    let mut features = FEATURE_TOKEN_2022 | FEATURE_RELAY | FEATURE_BATCH_RESCUE | FEATURE_EPOCH_STATS
+        | FEATURE_SCHEDULED_BURN | FEATURE_VAULT_RECOVERY;
+    if cfg!(feature = "compact-logs") {
+        features |= FEATURE_COMPACT_LOGS;
+    }
+    if cfg!(feature = "test-helpers") {
+        features |= FEATURE_TEST_HELPERS;
+    }
+    if cfg!(feature = "event-cpi") {
+        features |= FEATURE_EVENT_CPI;
+    }
+
+    let runtime = [
+        (FEATURE_FEES,             state.referral_bps > 0 || state.treasury_bps > 0),
+        (FEATURE_DAILY_LIMIT,      state.daily_burn_limit > 0),
+        (FEATURE_SUPPLY_GUARDRAIL, state.max_supply_pct_bps > 0),
+        (FEATURE_DUST_CLOSE,       state.dust_threshold > 0),
+        (FEATURE_MIN_WITHDRAW,     state.min_withdraw_lamports > 0),
+        (FEATURE_WITHDRAW_COOLDOWN, state.withdraw_cooldown_secs > 0),
+        (FEATURE_TOKEN_FEE,        state.token_fee_amount > 0),
+        (FEATURE_CIRCUIT_BREAKER,  state.anomaly_threshold > 0),
+        (FEATURE_FEE_WAIVER,       state.fees_waived()?),
+        (FEATURE_EVENTS,           state.event_level > EVENT_LEVEL_NONE),
+    ];
+    for (bit, enabled) in runtime {
+        if enabled {
+            features |= bit;
+        }
+    }
+
+    msg!("Supported features: {:#x}", features);
+    Ok(features)
+}
+
+pub(crate) fn freeze_receipt_mint(ctx: Context<FreezeReceiptMint>) -> Result<()> {
+    let state_seeds: &[&[u8]] = &[b"state", &[ctx.accounts.state.state_bump]];
+    let signer_seeds = &[state_seeds];
+
+    let cpi_accounts = SetAuthority {
+        current_authority: ctx.accounts.state.to_account_info(),
+        account_or_mint: ctx.accounts.receipt_mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    // None = no one can ever mint receipts again
+    set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+    msg!(
+        "Receipt mint {} frozen at supply {}",
+        ctx.accounts.receipt_mint.key(),
+        ctx.accounts.receipt_mint.supply
+    );
+    Ok(())
+}
+
+pub(crate) fn set_daily_burn_limit(ctx: Context<SetDailyBurnLimit>, limit: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.daily_burn_limit = limit;
+
+    msg!("Daily burn limit set to {} (0 = unlimited)", limit);
+    Ok(())
+}
+
+#[cfg(feature = "test-helpers")]
+pub(crate) fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: i64, epoch: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.mock_unix_timestamp = unix_timestamp;
+    state.mock_epoch          = epoch;
+
+    msg!("Mock clock set to {} in epoch {} (0 = cluster clock)", unix_timestamp, epoch);
+    Ok(())
+}
+
+pub(crate) fn set_dust_threshold(ctx: Context<SetDustThreshold>, threshold: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.dust_threshold = threshold;
+
+    msg!("Dust threshold set to {} (0 = strict empty-only closes)", threshold);
+    Ok(())
+}
+
+pub(crate) fn set_min_batch_count(ctx: Context<SetMinBatchCount>, count: u8) -> Result<()> {
+    // Above what fits in one batch, no batch could ever pass
+    require!(count as usize <= MAX_BATCH_ACCOUNTS / 3, BurnerError::BatchTooLarge);
+    ctx.accounts.state.min_batch_count = count;
+
+    msg!("Minimum batch size set to {} token accounts (0 = any)", count);
+    Ok(())
+}
+
+pub(crate) fn set_min_withdraw(ctx: Context<SetMinWithdraw>, lamports: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.min_withdraw_lamports = lamports;
+
+    msg!("Minimum withdrawal set to {} lamports (0 = any amount)", lamports);
+    Ok(())
+}
+
+pub(crate) fn set_withdraw_cooldown(ctx: Context<SetWithdrawCooldown>, secs: u32) -> Result<()> {
+    ctx.accounts.state.withdraw_cooldown_secs = secs;
+
+    msg!("Withdrawal cooldown set to {} seconds (0 = none)", secs);
+    Ok(())
+}
+
+pub(crate) fn set_anomaly_threshold(ctx: Context<SetAnomalyThreshold>, threshold: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let now = state.now()?;
+    state.anomaly_threshold = threshold;
+    // Measure against the new threshold from scratch, not volume from before it was set
+    state.reset_anomaly_window(now);
+
+    msg!("Circuit breaker threshold set to {} per {}s (0 = off)", threshold, ANOMALY_WINDOW_SECS);
+    Ok(())
+}
+
+#[cfg(feature = "test-helpers")]
+pub(crate) fn set_processing_flag(ctx: Context<SetProcessingFlag>, processing: bool) -> Result<()> {
+    ctx.accounts.state.processing = processing;
+
+    msg!("Processing flag forced to {}", processing);
+    Ok(())
+}
+
+#[cfg(feature = "test-helpers")]
+pub(crate) fn corrupt_vault(ctx: Context<CorruptVault>, bump: u8, owner: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.bump                = bump;
+    vault.owner               = owner;
+    vault.rent_floor_snapshot = 0;
+
+    msg!("Vault corrupted: bump {}, owner {}, rent floor snapshot 0", bump, owner);
+    Ok(())
+}
+
+#[cfg(feature = "test-helpers")]
+pub(crate) fn truncate_state(ctx: Context<TruncateState>, len: u32) -> Result<()> {
+    let info = ctx.accounts.state.to_account_info();
+    let authority = stored_leading_key(&info, BurnerState::DISCRIMINATOR)?;
+    require_keys_eq!(authority, ctx.accounts.authority.key(), BurnerError::UnauthorizedAuthority);
+    truncate_account(&info, len)?;
+
+    msg!("State truncated to {} bytes", len);
+    Ok(())
+}
+
+#[cfg(feature = "test-helpers")]
+pub(crate) fn truncate_vault(ctx: Context<TruncateVault>, len: u32) -> Result<()> {
+    let info = ctx.accounts.vault.to_account_info();
+    let owner = stored_leading_key(&info, VaultAccount::DISCRIMINATOR)?;
+    truncate_account(&info, len)?;
+
+    msg!("Vault of {} truncated to {} bytes", owner, len);
+    Ok(())
+}
+
+// Never below the leading key, or not even the migrations could read it back
+#[cfg(feature = "test-helpers")]
+fn truncate_account(info: &AccountInfo, len: u32) -> Result<()> {
+    require!(
+        len as usize >= 8 + 32 && (len as usize) < info.data_len(),
+        ErrorCode::AccountDidNotDeserialize
+    );
+    info.resize(len as usize)?;
+    Ok(())
+}
+
+pub(crate) fn set_max_supply_pct_bps(ctx: Context<SetMaxSupplyPctBps>, bps: u16) -> Result<()> {
+    require!(bps <= MAX_BPS, BurnerError::InvalidBps);
+
+    let state = &mut ctx.accounts.state;
+    state.max_supply_pct_bps = bps;
+
+    msg!("Max single-burn share of supply set to {} bps (0 = disabled)", bps);
+    Ok(())
+}
+
+pub(crate) fn set_event_level(ctx: Context<SetEventLevel>, level: u8) -> Result<()> {
+    require!(level <= EVENT_LEVEL_FULL, BurnerError::InvalidEventLevel);
+
+    let state = &mut ctx.accounts.state;
+    state.event_level = level;
+
+    msg!("Event level set to {}", level);
+    Ok(())
+}
+
+pub(crate) fn set_vault_creation_paused(ctx: Context<SetVaultCreationPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.state.vault_creation_paused = paused;
+
+    msg!("Vault creation {}", if paused { "paused" } else { "resumed" });
+    Ok(())
+}
+
+pub(crate) fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let now = state.now()?;
+    state.is_paused = paused;
+    if !paused {
+        // Otherwise the volume that tripped the breaker would trip it again on the next burn
+        state.reset_anomaly_window(now);
+    }
+
+    msg!("Burns {}", if paused { "paused" } else { "resumed" });
+    Ok(())
+}
+
+pub(crate) fn set_duplicate_close_check(ctx: Context<SetDuplicateCloseCheck>, enabled: bool) -> Result<()> {
+    ctx.accounts.state.check_duplicate_closes = enabled;
+
+    msg!("Duplicate close check {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+pub(crate) fn set_rent_split(
+    ctx: Context<SetRentSplit>,
+    referral_bps: u16,
+    treasury_bps: u16,
+    treasury: Pubkey,
+) -> Result<()> {
+    // The user's share is the remainder, so the other two can't exceed 100%
+    require!(
+        referral_bps as u32 + treasury_bps as u32 <= MAX_BPS as u32,
+        BurnerError::InvalidBps
+    );
+
+    let state = &mut ctx.accounts.state;
+    state.referral_bps = referral_bps;
+    state.treasury_bps = treasury_bps;
+    state.treasury     = treasury;
+
+    msg!(
+        "Rent split set: referral {} bps, treasury {} bps to {}",
+        referral_bps,
+        treasury_bps,
+        treasury
+    );
+    Ok(())
+}
+
+pub(crate) fn set_mint_fee(ctx: Context<SetMintFee>, fee_bps: u16) -> Result<()> {
+    // Same bound as set_rent_split: the referral share still comes out of the rent too
+    require!(
+        ctx.accounts.state.referral_bps as u32 + fee_bps as u32 <= MAX_BPS as u32,
+        BurnerError::InvalidBps
+    );
+
+    let mint_fee = &mut ctx.accounts.mint_fee;
+    mint_fee.mint    = ctx.accounts.mint.key();
+    mint_fee.fee_bps = fee_bps;
+    mint_fee.bump    = ctx.bumps.mint_fee;
+
+    msg!("Fee override for mint {} set to {} bps", mint_fee.mint, fee_bps);
+    Ok(())
+}
+
+pub(crate) fn set_mint_daily_cap(ctx: Context<SetMintDailyCap>, daily_cap: u64) -> Result<()> {
+    let limit = &mut ctx.accounts.mint_burn_limit;
+    limit.mint      = ctx.accounts.mint.key();
+    limit.daily_cap = daily_cap;
+    limit.bump      = ctx.bumps.mint_burn_limit;
+    // window_start / burned_today carry over, so changing the cap mid-window
+    // doesn't hand out a fresh allowance
+
+    msg!("Daily burn cap for mint {} set to {} (0 = uncapped)", limit.mint, daily_cap);
+    Ok(())
+}
+
+pub(crate) fn set_fee_waiver(ctx: Context<SetFeeWaiver>, until: i64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.fee_waiver_until = until;
+
+    msg!("Fees waived until {} (0 = no waiver)", until);
+    Ok(())
+}
+
+pub(crate) fn set_token_fee(ctx: Context<SetTokenFee>, amount: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.token_fee_amount   = amount;
+    state.token_fee_mint     = ctx.accounts.fee_mint.key();
+    state.token_fee_treasury = ctx.accounts.fee_treasury.key();
+
+    msg!(
+        "Token fee set to {} of mint {} into {} (0 = off)",
+        amount,
+        state.token_fee_mint,
+        state.token_fee_treasury
+    );
+    Ok(())
+}
+
+pub(crate) fn clear_mint_fee(ctx: Context<ClearMintFee>) -> Result<()> {
+    // Anchor's `close` constraint refunds the override's rent to the authority
+    msg!("Fee override for mint {} cleared", ctx.accounts.mint_fee.mint);
+    Ok(())
+}
+
+pub(crate) fn set_role(ctx: Context<SetRole>, role: u8, holder: Option<Pubkey>) -> Result<()> {
+    require!((role as usize) < NUM_ROLES, BurnerError::InvalidRole);
+
+    let state = &mut ctx.accounts.state;
+    state.roles[role as usize] = holder.unwrap_or_default();
+
+    match holder {
+        Some(holder) => msg!("Role {} delegated to {}", role, holder),
+        None => msg!("Role {} cleared, back to the main authority only", role),
+    }
+    Ok(())
+}
+
+pub(crate) fn add_token_program(ctx: Context<SetTokenProgramAllowlist>, program: Pubkey) -> Result<()> {
+    require!(program != Pubkey::default(), BurnerError::TokenProgramNotAllowed);
+    let state = &mut ctx.accounts.state;
+    if state.allows_token_program(&program) {
+        msg!("Token program {} already allowed", program);
+        return Ok(());
+    }
+    let slot = state
+        .token_program_allowlist
+        .iter_mut()
+        .find(|slot| **slot == Pubkey::default())
+        .ok_or(BurnerError::TokenProgramAllowlistFull)?;
+    *slot = program;
+
+    msg!("Token program {} allowed", program);
+    Ok(())
+}
+
+pub(crate) fn remove_token_program(ctx: Context<SetTokenProgramAllowlist>, program: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let slot = state
+        .token_program_allowlist
+        .iter_mut()
+        .find(|slot| **slot == program && program != Pubkey::default())
+        .ok_or(BurnerError::TokenProgramNotAllowed)?;
+    *slot = Pubkey::default();
+
+    msg!("Token program {} removed from the allowlist", program);
+    Ok(())
+}
+
+pub(crate) fn handover_to_dao(ctx: Context<HandoverToDao>, dao_seeds: Vec<Vec<u8>>, confirm: bool) -> Result<()> {
+    require!(confirm, BurnerError::HandoverNotConfirmed);
+
+    // Seeds include the bump; re-deriving proves the key is the DAO program's PDA,
+    // not a mistyped wallet that nobody could ever sign for
+    let seeds: Vec<&[u8]> = dao_seeds.iter().map(Vec::as_slice).collect();
+    let derived = Pubkey::create_program_address(&seeds, &ctx.accounts.dao_program.key())
+        .map_err(|_| BurnerError::InvalidDaoAuthority)?;
+    let dao_authority = ctx.accounts.dao_authority.key();
+    require_keys_eq!(derived, dao_authority, BurnerError::InvalidDaoAuthority);
+
+    let state = &mut ctx.accounts.state;
+    let old_authority = state.authority;
+    state.authority = dao_authority;
+    state.treasury  = dao_authority;
+    // Delegated roles belong to the outgoing team; the DAO re-delegates as it sees fit
+    state.roles     = [Pubkey::default(); NUM_ROLES];
+
+    emit!(DaoHandover {
+        old_authority,
+        dao_program: ctx.accounts.dao_program.key(),
+        dao_authority,
+    });
+    msg!("Authority and treasury handed over from {} to DAO PDA {}", old_authority, dao_authority);
+    Ok(())
+}
+
+pub(crate) fn backfill_vault_stats(
+    ctx: Context<BackfillVaultStats>,
+    lamports_collected: u64,
+    tokens_burned: u128,
+    accounts_closed: u64,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    require!(!vault.stats_backfilled, BurnerError::StatsAlreadyBackfilled);
+
+    // Authoritative values from indexed history, replace the post-migration counters
+    vault.lamports_collected = lamports_collected;
+    vault.tokens_burned      = tokens_burned;
+    vault.accounts_closed    = accounts_closed;
+    vault.stats_backfilled   = true;
+
+    msg!(
+        "Backfilled vault {} stats: {} lamports collected, {} tokens burned, {} accounts closed",
+        vault.owner,
+        lamports_collected,
+        tokens_burned,
+        accounts_closed
+    );
+    Ok(())
+}
+
+pub(crate) fn set_stats_resettable(ctx: Context<SetStatsResettable>, resettable: bool) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(!state.stats_reset_locked, BurnerError::StatsResetLocked);
+    state.stats_resettable = resettable;
+
+    msg!("Stats reset {}", if resettable { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+pub(crate) fn lock_stats_reset(ctx: Context<SetStatsResettable>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.stats_resettable   = false;
+    state.stats_reset_locked = true;
+
+    msg!("Stats reset locked off permanently");
+    Ok(())
+}
+
+pub(crate) fn reset_stats(ctx: Context<ResetStats>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(state.stats_resettable, BurnerError::StatsNotResettable);
+
+    // Only the lifetime tallies; the daily window is a limit, not a statistic
+    state.total_burned          = 0;
+    state.accounts_closed       = 0;
+    state.closed_vault_lamports = 0;
+    state.closed_vault_tokens_burned = 0;
+    state.closed_vault_accounts_closed = 0;
+    state.total_rent_reclaimed  = 0;
+    state.last_folded_at        = 0;
+
+    let vault = ctx.accounts.vault.as_mut().map(|vault| {
+        vault.lamports_collected = 0;
+        vault.tokens_burned      = 0;
+        vault.accounts_closed    = 0;
+        vault.owner
+    });
+
+    msg!("Global stats reset{}", if vault.is_some() { ", with one vault" } else { "" });
+    emit!(StatsReset {
+        authority: ctx.accounts.authority.key(),
+        vault,
+    });
+    Ok(())
+}
+
+pub(crate) fn migrate_treasury(ctx: Context<MigrateTreasury>, close_old: bool) -> Result<()> {
+    let old_treasury = ctx.accounts.old_treasury.key();
+    let new_treasury = ctx.accounts.new_treasury.key();
+    require_keys_neq!(old_treasury, new_treasury, BurnerError::TreasuryUnchanged);
+
+    // Accrued fees are whatever sits above the account's own rent floor;
+    // closing takes the floor too, which deletes the drained wallet
+    let old_ai = ctx.accounts.old_treasury.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(old_ai.data_len());
+    let lamports = if close_old {
+        old_ai.lamports()
+    } else {
+        old_ai.lamports().saturating_sub(rent_floor)
+    };
+
+    if lamports > 0 {
+        let transfer_accounts = Transfer {
+            from: old_ai,
+            to: ctx.accounts.new_treasury.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts),
+            lamports,
+        )?;
+    }
+    ctx.accounts.state.treasury = new_treasury;
+
+    emit!(TreasuryMigrated {
+        old_treasury,
+        new_treasury,
+        lamports,
+        closed: close_old,
+    });
+    msg!("Treasury migrated {} -> {}, {} lamports moved", old_treasury, new_treasury, lamports);
+    Ok(())
+}
+
+pub(crate) fn migrate_state_bump(ctx: Context<MigrateStateBump>) -> Result<()> {
+    let info = ctx.accounts.state.to_account_info();
+    let authority = stored_leading_key(&info, BurnerState::DISCRIMINATOR)?;
+    require_keys_eq!(authority, ctx.accounts.authority.key(), BurnerError::UnauthorizedAuthority);
+
+    // Fields only ever went on the end since `mock_epoch`, so an older account is a
+    // prefix of today's layout: growing it leaves every stored value where it was,
+    // and the zeroed tail reads as each newer field's off / unset value
+    let old_len = info.data_len();
+    let new_len = 8 + BurnerState::INIT_SPACE;
+    grow_account(
+        &info,
+        new_len,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut state = BurnerState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    state.state_bump = ctx.bumps.state;
+    state.version    = STATE_VERSION;
+    state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    msg!("State bump stored: {}, account {} -> {} bytes", state.state_bump, old_len, new_len);
+    Ok(())
+}
+
+/// The leading `Pubkey` of one of this program's accounts, read by hand after
+/// checking owner and `discriminator`: `BurnerState.authority` and
+/// `VaultAccount.owner` are first fields, at the same offset in every layout.
+pub(crate) fn stored_leading_key(info: &AccountInfo, discriminator: &[u8]) -> Result<Pubkey> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 + 32 && &data[..8] == discriminator,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(Pubkey::new_from_array(data[8..40].try_into().unwrap()))
+}
+
+/// Grows a program account to `new_len`, `payer` topping up its rent; the new tail
+/// is zeroed. An account that's already that long is left as it is.
+pub(crate) fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if info.data_len() >= new_len {
+        return Ok(());
+    }
+    let top_up = Rent::get()?.minimum_balance(new_len).saturating_sub(info.lamports());
+    if top_up > 0 {
+        let transfer_accounts = Transfer {
+            from: payer.clone(),
+            to: info.clone(),
+        };
+        system_program::transfer(CpiContext::new(system_program.clone(), transfer_accounts), top_up)?;
+    }
+    info.resize(new_len)?;
+    Ok(())
+}
+
+pub(crate) fn admin_close_empty(ctx: Context<AdminCloseEmpty>) -> Result<()> {
+    let token_account = &ctx.accounts.token_account;
+
+    // Support tool, not seizure: only empty accounts, and only ones whose owner
+    // opted in by handing their close authority to the state PDA
+    require!(token_account.amount == 0, BurnerError::AccountNotEmpty);
+    require!(
+        token_account.close_authority == COption::Some(ctx.accounts.state.key()),
+        BurnerError::InvalidCloseAuthority
+    );
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+    let state_seeds: &[&[u8]] = &[b"state", &[ctx.accounts.state.state_bump]];
+    let signer_seeds = &[state_seeds];
+
+    // Rent goes to the token owner's own vault, never to the admin
+    let cpi_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.state.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, 0)?;
+
+    msg!(
+        "Admin closed empty token account for {}, {} lamports sent to their vault",
+        ctx.accounts.vault.owner,
+        rent_lamports
+    );
+    Ok(())
+}
+
+// Account context for `initialize`
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// payer & future authority must sign to create the state PDA.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Program-Derived Account that stores state.
+    ///   • `init`       – create it if it doesn't exist
+    ///   • `payer`      – who funds rent
+    ///   • `space`      – bytes to allocate (8-byte Anchor discriminator + our struct)
+    ///   • `seeds`      – derive address from static seed `b"state"`
+    ///   • `bump`       – auto-adds the bump so the derive matches on-chain 
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BurnerState::INIT_SPACE,   // 8‑byte discriminator + struct size
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, BurnerState>,   
+     
+    /// System program (required by `init` to create accounts)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `migrate_state_bump`
+#[derive(Accounts)]
+pub struct MigrateStateBump<'info> {
+    /// Program authority stored on the state PDA; pays for growing it
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The one context that must re-derive: `state_bump` is what it's filling in
+    /// CHECK: may still be on an older, shorter layout; owner, discriminator and
+    /// authority are checked in the handler before it's read as `BurnerState`
+    #[account(mut, seeds = [b"state"], bump)]
+    pub state: UncheckedAccount<'info>,
+
+    /// System program (tops up the rent of the grown account)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `get_config`
+#[derive(Accounts)]
+pub struct GetConfig<'info> {
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read-only, no signer needed
+}
+
+// Account context for `global_dashboard`
+#[derive(Accounts)]
+pub struct GlobalDashboard<'info> {
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read-only, no signer needed
+}
+
+// Account context for `program_version`
+#[derive(Accounts)]
+pub struct ProgramVersionView<'info> {
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read-only, no signer needed
+}
+
+// Account context for `supported_features`
+#[derive(Accounts)]
+pub struct SupportedFeatures<'info> {
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read-only, no signer needed
+}
+
+// Account context for `freeze_receipt_mint`
+#[derive(Accounts)]
+pub struct FreezeReceiptMint<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    // The stored bump also signs the SetAuthority CPI
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>, // current receipt mint authority
+
+    /// Receipt mint whose mint authority is the state PDA
+    #[account(
+        mut,
+        constraint = receipt_mint.mint_authority == COption::Some(state.key()) @ BurnerError::InvalidMintAuthority
+    )]
+    pub receipt_mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+}
+
+// Account context for `set_daily_burn_limit`
+#[derive(Accounts)]
+pub struct SetDailyBurnLimit<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_max_supply_pct_bps`
+#[derive(Accounts)]
+pub struct SetMaxSupplyPctBps<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_dust_threshold`
+#[derive(Accounts)]
+pub struct SetDustThreshold<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_min_batch_count`
+#[derive(Accounts)]
+pub struct SetMinBatchCount<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_min_withdraw`
+#[derive(Accounts)]
+pub struct SetMinWithdraw<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_withdraw_cooldown`
+#[derive(Accounts)]
+pub struct SetWithdrawCooldown<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_anomaly_threshold`
+#[derive(Accounts)]
+pub struct SetAnomalyThreshold<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_event_level`
+#[derive(Accounts)]
+pub struct SetEventLevel<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_vault_creation_paused`
+#[derive(Accounts)]
+pub struct SetVaultCreationPaused<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_paused`
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_duplicate_close_check`
+#[derive(Accounts)]
+pub struct SetDuplicateCloseCheck<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_mock_clock`
+#[cfg(feature = "test-helpers")]
+#[derive(Accounts)]
+pub struct SetMockClock<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `corrupt_vault`
+#[cfg(feature = "test-helpers")]
+#[derive(Accounts)]
+pub struct CorruptVault<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Any user's vault; re-derived from its stored owner
+    #[account(
+        mut,
+        seeds = [b"vault", vault.owner.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+}
+
+// Account context for `truncate_state`
+#[cfg(feature = "test-helpers")]
+#[derive(Accounts)]
+pub struct TruncateState<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    /// CHECK: read by hand in the handler, like `migrate_state_bump` does
+    #[account(mut, seeds = [b"state"], bump)]
+    pub state: UncheckedAccount<'info>,
+}
+
+// Account context for `truncate_vault`
+#[cfg(feature = "test-helpers")]
+#[derive(Accounts)]
+pub struct TruncateVault<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: any user's vault; owner and discriminator are checked in the handler,
+    /// which is all a shorter, older layout still has in common with today's
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+}
+
+// Account context for `set_processing_flag`
+#[cfg(feature = "test-helpers")]
+#[derive(Accounts)]
+pub struct SetProcessingFlag<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_rent_split`
+#[derive(Accounts)]
+pub struct SetRentSplit<'info> {
+    /// Program authority, or the `ROLE_FEES` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_FEES, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_mint_fee`
+#[derive(Accounts)]
+pub struct SetMintFee<'info> {
+    /// Program authority, or the `ROLE_FEES` holder; pays for a new override
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_FEES, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Mint the override applies to
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintFeeOverride::INIT_SPACE,
+        seeds = [b"mint_fee", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee: Account<'info, MintFeeOverride>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `set_mint_daily_cap`
+#[derive(Accounts)]
+pub struct SetMintDailyCap<'info> {
+    /// Program authority, or the `ROLE_LIMITS` holder; pays for a new limit
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_LIMITS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Mint the cap applies to
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintBurnLimit::INIT_SPACE,
+        seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_burn_limit: Account<'info, MintBurnLimit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `set_fee_waiver`
+#[derive(Accounts)]
+pub struct SetFeeWaiver<'info> {
+    /// Program authority, or the `ROLE_FEES` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_FEES, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `set_token_fee`
+#[derive(Accounts)]
+pub struct SetTokenFee<'info> {
+    /// Program authority, or the `ROLE_FEES` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_FEES, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Mint the fee is paid in, SPL Token or Token-2022
+    pub fee_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// Token account that receives the fee
+    #[account(constraint = fee_treasury.mint == fee_mint.key() @ BurnerError::InvalidTokenFeeAccount)]
+    pub fee_treasury: InterfaceAccount<'info, token_interface::TokenAccount>,
+}
+
+// Account context for `clear_mint_fee`
+#[derive(Accounts)]
+pub struct ClearMintFee<'info> {
+    /// Program authority, or the `ROLE_FEES` holder; receives the override's rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_FEES, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"mint_fee", mint_fee.mint.as_ref()],
+        bump = mint_fee.bump
+    )]
+    pub mint_fee: Account<'info, MintFeeOverride>,
+}
+
+// Account context for `migrate_treasury`
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    /// Program authority, or the `ROLE_FEES` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_FEES, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// The configured treasury; a plain wallet, so it signs for its own balance
+    #[account(mut, address = state.treasury @ BurnerError::InvalidOwner)]
+    pub old_treasury: Signer<'info>,
+
+    /// CHECK: lamport destination only, becomes `state.treasury`
+    #[account(mut)]
+    pub new_treasury: UncheckedAccount<'info>,
+
+    /// System program (moves the lamports)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `set_role`
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    /// Program authority stored on the state PDA; role holders can't re-delegate
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `add_token_program` and `remove_token_program`
+#[derive(Accounts)]
+pub struct SetTokenProgramAllowlist<'info> {
+    /// Program authority stored on the state PDA; no role, it decides which code gets CPIs
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `handover_to_dao`
+#[derive(Accounts)]
+pub struct HandoverToDao<'info> {
+    /// Program authority stored on the state PDA; no role can give the program away
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: governance program the new authority is a PDA of; only its key is used
+    #[account(executable)]
+    pub dao_program: UncheckedAccount<'info>,
+
+    /// CHECK: the DAO's signing PDA, re-derived from `dao_seeds` in the handler
+    pub dao_authority: UncheckedAccount<'info>,
+}
+
+// Account context for `set_stats_resettable` and `lock_stats_reset`
+#[derive(Accounts)]
+pub struct SetStatsResettable<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `reset_stats`
+#[derive(Accounts)]
+pub struct ResetStats<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Optional vault whose `lamports_collected`, `tokens_burned` and `accounts_closed`
+    /// are zeroed as well; `stats_backfilled` stays set, a backfill is still once only
+    #[account(mut, seeds = [b"vault", vault.owner.as_ref()], bump = vault.bump)]
+    pub vault: Option<Account<'info, VaultAccount>>,
+}
+
+// Account context for `backfill_vault_stats`
+#[derive(Accounts)]
+pub struct BackfillVaultStats<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Any user's vault; re-derived from its stored owner
+    #[account(
+        mut,
+        seeds = [b"vault", vault.owner.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+}
+
+// Account context for `admin_close_empty`
+#[derive(Accounts)]
+pub struct AdminCloseEmpty<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    /// Also the token account's close authority, signs the close via its seeds
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Empty SPL Token or Token-2022 account of any user
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Vault of the token account's owner – the only allowed rent destination
+    #[account(
+        mut,
+        seeds = [b"vault", token_account.owner.as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == token_account.owner @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,592 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, CloseAccount, close_account, Burn, burn, TokenAccount, TransferChecked, transfer_checked};
+
+use crate::constants::{
+    BATCH_BASE_CU, BATCH_CU_PER_ACCOUNT, BATCH_STATUS_BURNED, BATCH_STATUS_PAIR_MISMATCH, BATCH_STATUS_RESCUED,
+    CLASS_BURNABLE, CLASS_EMPTY, ESTIMATED_TX_FEE_LAMPORTS, EVENT_LEVEL_FULL, EVENT_LEVEL_ROLLUP, MAX_BATCH_ACCOUNTS, MAX_BATCH_RESULTS, MAX_CU_LIMIT, RECOMMENDED_CU_LIMIT,
+};
+use crate::errors::BurnerError;
+use crate::events::{BatchAccountResult, BatchCompleted, TokenAccountBurned, TokenAccountRescued};
+use crate::instructions::close::{record_reclaimed_rent, require_close_authority, require_not_program_account};
+use crate::instructions::validate::classify_account;
+use crate::logs;
+use crate::state::{BurnerState, EpochStats, MintBurnLimit, ReclaimCache, ShardCounter, VaultAccount};
+
+// Per-entry outcome of `batch_burn_and_close`, returned in `BatchOutcome::results`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchResult {
+    pub pubkey: Pubkey,   // token account of the entry
+    pub status_code: u32, // BATCH_STATUS_* or the error code it was skipped with
+    pub rent: u64,        // lamports its close sent to the vault, 0 if skipped
+}
+
+// Return-data payload of `batch_burn_and_close`. `results` comes first, so its
+// layout is what clients decoding a bare `Vec<BatchResult>` already expect.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchOutcome {
+    pub results: Vec<BatchResult>, // one per processed entry, empty past MAX_BATCH_RESULTS
+    pub next_index: u16,           // remaining_accounts index to resume from; the list's length when done
+}
+
+// Return-data payload of `preview_batch_net` – what cleaning up a set of accounts nets the user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchNetPreview {
+    pub closable: u32,      // accounts the user could burn/close (burnable or empty)
+    pub gross_rent: u64,    // lamports those accounts hold, all reclaimed on close
+    pub protocol_fee: u64,  // referral + treasury shares of the configured rent split
+    pub tx_fee: u64,        // ESTIMATED_TX_FEE_LAMPORTS per batch transaction needed
+    pub net: i64,           // gross_rent - protocol_fee - tx_fee, negative when it doesn't pay
+}
+
+pub(crate) fn batch_burn_and_close<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchBurnAndClose<'info>>,
+    allow_partial: bool,
+    start_index: u16,
+) -> Result<BatchOutcome> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let remaining = ctx.remaining_accounts;
+    require!(remaining.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+    // Every entry is (token_account, mint, mint_burn_limit); rescue mode appends a
+    // rescue token account
+    let rescue_owner = ctx.accounts.rescue_owner.as_ref().map(|owner| owner.key());
+    let stride = if rescue_owner.is_some() { 4 } else { 3 };
+    let entries = remaining.chunks_exact(stride);
+    require!(
+        !remaining.is_empty() && entries.remainder().is_empty(),
+        BurnerError::InvalidBatchAccounts
+    );
+    // Counted in entries, so rescue mode's fourth account per entry doesn't change the bar.
+    // A resumed tail is still part of the full batch, so the whole list is what's counted.
+    require!(
+        entries.len() >= ctx.accounts.state.min_batch_count as usize,
+        BurnerError::BatchTooSmall
+    );
+    // Resuming must land on an entry boundary, inside the list
+    let start = start_index as usize;
+    require!(
+        start.is_multiple_of(stride) && start < remaining.len(),
+        BurnerError::InvalidBatchAccounts
+    );
+    let first_entry = start / stride;
+
+    let user_key = ctx.accounts.user.key();
+    let now = ctx.accounts.state.now()?;
+    let full_events = ctx.accounts.state.emits(EVENT_LEVEL_FULL);
+    let mut processed: u32 = 0;
+    let mut burned: u64 = 0;
+    let mut rent_total: u64 = 0;
+    let mut results: Vec<BatchResult> = Vec::with_capacity(entries.len() - first_entry);
+    let mut next_index = remaining.len();
+    let legacy_program = ctx.accounts.token_program.to_account_info();
+    let token_2022_program = ctx.accounts.token_2022_program.as_ref().map(|program| program.to_account_info());
+
+    for (index, entry) in entries.enumerate().skip(first_entry) {
+        // Stop while the wrap-up below still fits, so a batch that runs out of compute
+        // commits what it did and reports where to resume instead of failing outright
+        if sol_remaining_compute_units() < (BATCH_CU_PER_ACCOUNT + BATCH_BASE_CU) as u64 {
+            next_index = index * stride;
+            break;
+        }
+        let (token_account_info, mint_info, limit_info) = (&entry[0], &entry[1], &entry[2]);
+
+        let validated = validate_batch_pair(
+            token_account_info,
+            mint_info,
+            &user_key,
+            &legacy_program,
+            token_2022_program.as_ref(),
+        );
+        let (token_account, token_program) = match validated {
+            Ok(validated) => validated,
+            // Misaligned pair: never burn against the wrong mint, skip it either way
+            Err(err) if error_code(&err) == u32::from(BurnerError::MintMismatch) => {
+                logs::batch_skipped(index, &token_account_info.key(), &err);
+                results.push(BatchResult {
+                    pubkey:      token_account_info.key(),
+                    status_code: BATCH_STATUS_PAIR_MISMATCH,
+                    rent:        0,
+                });
+                continue;
+            }
+            Err(err) if allow_partial => {
+                logs::batch_skipped(index, &token_account_info.key(), &err);
+                results.push(BatchResult {
+                    pubkey:      token_account_info.key(),
+                    status_code: error_code(&err),
+                    rent:        0,
+                });
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        // The mint's cap is checked on a copy and only written back once the global
+        // limit also passed, so a rescued entry books neither
+        let mut mint_limit = MintBurnLimit::load_for_mint(limit_info, &token_account.mint)?;
+        let booked = match mint_limit.as_mut() {
+            Some(limit) => limit.record(token_account.amount, now),
+            None => Ok(()),
+        }
+        .and_then(|()| ctx.accounts.state.record_burn(token_account.amount, now));
+
+        // Both limits hold across entries: without a rescue owner, exceeding one aborts even in partial mode
+        let status_code = match (booked, rescue_owner) {
+            // Burn path
+            (Ok(()), _) => {
+                if let Some(limit) = &mint_limit {
+                    limit.save(limit_info)?;
+                }
+                if token_account.amount > 0 {
+                    let burn_accounts = Burn {
+                        mint: mint_info.clone(),
+                        from: token_account_info.clone(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    };
+                    let burn_ctx = CpiContext::new(token_program.clone(), burn_accounts);
+                    burn(burn_ctx, token_account.amount)?;
+                }
+                burned = burned
+                    .checked_add(token_account.amount)
+                    .ok_or(BurnerError::MathOverflow)?;
+
+                if full_events {
+                    emit!(TokenAccountBurned {
+                        user:          user_key,
+                        token_account: token_account_info.key(),
+                        mint:          token_account.mint,
+                        amount:        token_account.amount,
+                    });
+                }
+                BATCH_STATUS_BURNED
+            }
+            // Rescue path
+            (Err(err), Some(rescue_owner)) => {
+                let rescue_info = &entry[3];
+                validate_rescue_account(rescue_info, &token_account.mint, &rescue_owner, &token_program.key())?;
+                logs::batch_rescued(index, &token_account_info.key(), &err);
+
+                if token_account.amount > 0 {
+                    let decimals = token_interface::Mint::try_deserialize(&mut &mint_info.data.borrow()[..])?.decimals;
+                    let transfer_accounts = TransferChecked {
+                        from: token_account_info.clone(),
+                        mint: mint_info.clone(),
+                        to: rescue_info.clone(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    };
+                    let transfer_ctx = CpiContext::new(token_program.clone(), transfer_accounts);
+                    transfer_checked(transfer_ctx, token_account.amount, decimals)?;
+                }
+
+                if full_events {
+                    emit!(TokenAccountRescued {
+                        user:           user_key,
+                        token_account:  token_account_info.key(),
+                        mint:           token_account.mint,
+                        amount:         token_account.amount,
+                        rescue_account: rescue_info.key(),
+                    });
+                }
+                BATCH_STATUS_RESCUED
+            }
+            (Err(err), None) => return Err(err),
+        };
+
+        let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+        let close_accounts = CloseAccount {
+            account: token_account_info.clone(),
+            destination: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let close_ctx = CpiContext::new(token_program, close_accounts);
+        close_account(close_ctx)?;
+        // Measured from the vault's lamport delta per entry, never assumed: a Token-2022
+        // account with extensions holds more rent than a 165-byte legacy one, and any
+        // account can carry lamports above its rent
+        let tokens_burned = if status_code == BATCH_STATUS_BURNED { token_account.amount } else { 0 };
+        let rent = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, tokens_burned)?;
+        rent_total = rent_total.saturating_add(rent);
+
+        processed += 1;
+        results.push(BatchResult {
+            pubkey: token_account_info.key(),
+            status_code,
+            rent,
+        });
+    }
+
+    if let Some(shard) = ctx.accounts.shard.as_mut() {
+        shard.record(burned, processed, rent_total)?;
+    }
+    if let Some(epoch_stats) = ctx.accounts.epoch_stats.as_mut() {
+        epoch_stats.record(ctx.accounts.state.epoch()?, ctx.bumps.epoch_stats.unwrap_or_default(), burned, processed)?;
+    }
+
+    logs::batch_summary(processed, results.len(), rent_total);
+
+    if ctx.accounts.state.emits(EVENT_LEVEL_ROLLUP) {
+        emit!(BatchCompleted {
+            user:      user_key,
+            processed,
+            skipped:   results.len() as u32 - processed,
+            burned,
+            rent:      rent_total,
+        });
+    }
+
+    // Return data caps at 1024 bytes; past that, every result goes out as an event
+    // instead – which is only possible at EVENT_LEVEL_FULL
+    if results.len() > MAX_BATCH_RESULTS {
+        if full_events {
+            for result in &results {
+                emit!(BatchAccountResult {
+                    pubkey:      result.pubkey,
+                    status_code: result.status_code,
+                    rent:        result.rent,
+                });
+            }
+        }
+        ctx.accounts.state.end_processing();
+        return Ok(BatchOutcome {
+            results: Vec::new(),
+            next_index: next_index as u16,
+        });
+    }
+    ctx.accounts.state.end_processing();
+    Ok(BatchOutcome {
+        results,
+        next_index: next_index as u16,
+    })
+}
+
+/// Numeric code of a skipped entry's error, as clients see it in transaction errors.
+pub(crate) fn error_code(err: &Error) -> u32 {
+    match err {
+        Error::AnchorError(err) => err.error_code_number,
+        Error::ProgramError(err) => u64::from(err.program_error.clone()) as u32,
+    }
+}
+
+pub(crate) fn preview_cu_for_batch(_ctx: Context<PreviewCuForBatch>, n: u32) -> Result<u32> {
+    let limit = BATCH_BASE_CU
+        .saturating_add(BATCH_CU_PER_ACCOUNT.saturating_mul(n))
+        .clamp(RECOMMENDED_CU_LIMIT, MAX_CU_LIMIT);
+
+    msg!("Recommended CU limit for {} accounts: {}", n, limit);
+    Ok(limit)
+}
+
+pub(crate) fn preview_batch_net(ctx: Context<PreviewBatchNet>) -> Result<BatchNetPreview> {
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+
+    let user = ctx.accounts.user.key();
+    let state = &ctx.accounts.state;
+    let mut closable: u32 = 0;
+    let mut gross_rent: u64 = 0;
+    let mut protocol_fee: u64 = 0;
+
+    for info in ctx.remaining_accounts {
+        let class = classify_account(info, &user);
+        if class != CLASS_BURNABLE && class != CLASS_EMPTY {
+            continue;
+        }
+        // Split per account, as burn_close_refer does, so the rounding matches
+        let rent = info.lamports();
+        let (_, referral, treasury) = state.split_rent(rent, None)?;
+        closable += 1;
+        gross_rent = gross_rent.checked_add(rent).ok_or(BurnerError::MathOverflow)?;
+        protocol_fee = protocol_fee
+            .checked_add(referral + treasury)
+            .ok_or(BurnerError::MathOverflow)?;
+    }
+
+    // Each account travels with its mint and mint limit, so a batch transaction fits a third of the cap
+    let entries_per_tx = (MAX_BATCH_ACCOUNTS / 3) as u32;
+    let tx_fee = ESTIMATED_TX_FEE_LAMPORTS * closable.div_ceil(entries_per_tx) as u64;
+    let net = gross_rent as i64 - protocol_fee as i64 - tx_fee as i64;
+
+    msg!(
+        "{} closable accounts: {} rent - {} protocol fee - {} tx fee = {} lamports net",
+        closable,
+        gross_rent,
+        protocol_fee,
+        tx_fee,
+        net
+    );
+    Ok(BatchNetPreview {
+        closable,
+        gross_rent,
+        protocol_fee,
+        tx_fee,
+        net,
+    })
+}
+
+pub(crate) fn cleanup_all<'info>(ctx: Context<'_, '_, 'info, 'info, CleanupAll<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(!remaining.is_empty(), BurnerError::InvalidBatchAccounts);
+    require!(remaining.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+    // Fail before closing anything if the payout at the end can't happen
+    ctx.accounts.vault.check_cosigner(ctx.accounts.cosigner.as_ref())?;
+
+    let user = ctx.accounts.user.key();
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+    for info in remaining {
+        let token_program = if *info.owner == Token::id() {
+            ctx.accounts.token_program.to_account_info()
+        } else if *info.owner == Token2022::id() {
+            ctx.accounts
+                .token_2022_program
+                .as_ref()
+                .ok_or(BurnerError::Token2022ProgramMissing)?
+                .to_account_info()
+        } else {
+            return err!(BurnerError::NotATokenAccount);
+        };
+        let token_account = TokenAccount::try_deserialize(&mut &info.data.borrow()[..])
+            .map_err(|_| BurnerError::NotATokenAccount)?;
+        require!(token_account.owner == user, BurnerError::UnauthorizedAccount);
+        require!(token_account.amount == 0, BurnerError::AccountNotEmpty);
+        require_close_authority(token_account.close_authority, &user)?;
+        require_not_program_account(&info.key(), &[ctx.accounts.vault.key(), ctx.accounts.state.key()])?;
+
+        let close_accounts = CloseAccount {
+            account: info.clone(),
+            destination: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        close_account(CpiContext::new(token_program, close_accounts))?;
+    }
+    let rent = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, remaining.len() as u64, 0)?;
+
+    // Then the payout, with the same guards as withdraw_vault
+    let vault_ai = ctx.accounts.vault.to_account_info();
+    let withdrawable = ctx.accounts.vault.usable_lamports(&vault_ai)?;
+    require!(
+        withdrawable >= ctx.accounts.state.min_withdraw_lamports,
+        BurnerError::BelowMinWithdraw
+    );
+    if withdrawable > 0 {
+        let now = ctx.accounts.state.now()?;
+        ctx.accounts.vault.record_withdrawal(ctx.accounts.state.withdraw_cooldown_secs, now)?;
+        **vault_ai.try_borrow_mut_lamports()? -= withdrawable;
+        **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += withdrawable;
+    }
+
+    msg!(
+        "Closed {} accounts for {} lamports, withdrew {} lamports to the owner",
+        remaining.len(),
+        rent,
+        withdrawable
+    );
+    Ok(())
+}
+
+pub(crate) fn refresh_reclaim_cache(ctx: Context<RefreshReclaimCache>) -> Result<()> {
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+
+    let user = ctx.accounts.user.key();
+    let mut closable: u32 = 0;
+    let mut total_reclaimable: u64 = 0;
+    // Same test as preview_batch_net, so the cached total matches a fresh preview's gross_rent
+    for info in ctx.remaining_accounts {
+        let class = classify_account(info, &user);
+        if class != CLASS_BURNABLE && class != CLASS_EMPTY {
+            continue;
+        }
+        closable += 1;
+        total_reclaimable = total_reclaimable
+            .checked_add(info.lamports())
+            .ok_or(BurnerError::MathOverflow)?;
+    }
+
+    let cache = &mut ctx.accounts.reclaim_cache;
+    cache.owner = user;
+    cache.bump = ctx.bumps.reclaim_cache;
+    cache.closable = closable;
+    cache.total_reclaimable = total_reclaimable;
+    cache.refreshed_at = ctx.accounts.state.now()?;
+    cache.refreshed_slot = Clock::get()?.slot;
+
+    msg!(
+        "Reclaim cache for {}: {} closable accounts, {} lamports",
+        user,
+        closable,
+        total_reclaimable
+    );
+    Ok(())
+}
+
+/// Checks one `(token_account, mint)` pair of a batch before any CPI touches it,
+/// and picks the token program the entry's CPIs go to: the one that owns it.
+fn validate_batch_pair<'info>(
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo,
+    user: &Pubkey,
+    legacy_program: &AccountInfo<'info>,
+    token_2022_program: Option<&AccountInfo<'info>>,
+) -> Result<(TokenAccount, AccountInfo<'info>)> {
+    let token_program = if *token_account_info.owner == Token::id() {
+        legacy_program.clone()
+    } else if *token_account_info.owner == Token2022::id() {
+        token_2022_program.cloned().ok_or(BurnerError::Token2022ProgramMissing)?
+    } else {
+        return err!(BurnerError::NotATokenAccount);
+    };
+    let token_account = TokenAccount::try_deserialize(&mut &token_account_info.data.borrow()[..])
+        .map_err(|_| BurnerError::NotATokenAccount)?;
+
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == *user,
+        BurnerError::UnauthorizedAccount
+    );
+    require!(
+        token_account.mint == mint_info.key(),
+        BurnerError::MintMismatch
+    );
+    // Frozen accounts can be neither burned nor closed
+    require!(!token_account.is_frozen(), BurnerError::AccountFrozen);
+    require_close_authority(token_account.close_authority, user)?;
+
+    Ok((token_account, token_program))
+}
+
+/// Checks the rescue destination of a rescue-mode batch entry: a token account of the same
+/// mint and token program, owned by the batch's `rescue_owner`.
+fn validate_rescue_account(
+    rescue_info: &AccountInfo,
+    mint: &Pubkey,
+    rescue_owner: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(*rescue_info.owner, *token_program, BurnerError::InvalidRescueAccount);
+    let rescue_account = TokenAccount::try_deserialize(&mut &rescue_info.data.borrow()[..])
+        .map_err(|_| BurnerError::InvalidRescueAccount)?;
+
+    require!(
+        rescue_account.owner == *rescue_owner && rescue_account.mint == *mint,
+        BurnerError::InvalidRescueAccount
+    );
+    Ok(())
+}
+
+// Account context for `batch_burn_and_close`
+// Token accounts, mints and the mints' `MintBurnLimit` PDAs are passed as remaining_accounts
+// triples (quadruples in rescue mode).
+#[derive(Accounts)]
+pub struct BatchBurnAndClose<'info> {
+    /// User who owns every token account in the batch
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// User's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: only its key is used, to match rescue token accounts. Enables rescue mode when present
+    pub rescue_owner: Option<UncheckedAccount<'info>>,
+
+    /// Optional shard counter to book this burn on; the client picks the index
+    #[account(mut, seeds = [b"shard".as_ref(), &[shard.index]], bump = shard.bump)]
+    pub shard: Option<Account<'info, ShardCounter>>,
+
+    /// Optional rollup for the current epoch, created by the first burn that passes it
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + EpochStats::INIT_SPACE,
+        seeds = [b"epoch".as_ref(), &state.epoch()?.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Option<Account<'info, EpochStats>>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Token-2022 program; required only when the batch holds Token-2022 accounts
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+
+    /// System program (required by `init_if_needed` to create `epoch_stats`)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `preview_cu_for_batch` – pure computation, no accounts
+#[derive(Accounts)]
+pub struct PreviewCuForBatch {}
+
+// Account context for `preview_batch_net` – read-only
+// Token accounts to preview are passed as remaining_accounts, without their mints.
+#[derive(Accounts)]
+pub struct PreviewBatchNet<'info> {
+    /// CHECK: wallet the preview is for; read-only and need not sign
+    pub user: UncheckedAccount<'info>,
+
+    /// Global state PDA, read for the rent split
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+}
+
+// Account context for `cleanup_all`
+// Empty token accounts to close are passed as remaining_accounts, without their mints.
+#[derive(Accounts)]
+pub struct CleanupAll<'info> {
+    /// Owner of every token account; receives the vault's usable lamports
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read for min_withdraw_lamports and withdraw_cooldown_secs
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Token-2022 program; required only when the list holds Token-2022 accounts
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+
+    pub cosigner: Option<Signer<'info>>, // required when the vault has a required_cosigner
+}
+
+// Account context for `refresh_reclaim_cache`
+// Token accounts to count are passed as remaining_accounts, without their mints.
+#[derive(Accounts)]
+pub struct RefreshReclaimCache<'info> {
+    /// Wallet the estimate is for; pays the cache's rent on the first refresh
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ReclaimCache::INIT_SPACE,
+        seeds = [b"reclaim_cache", user.key().as_ref()],
+        bump
+    )]
+    pub reclaim_cache: Account<'info, ReclaimCache>,
+
+    /// Global state PDA, read for the clock
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    pub system_program: Program<'info, System>,
+}
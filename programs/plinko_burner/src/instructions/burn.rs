@@ -0,0 +1,1382 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Burn, MintTo, burn, mint_to};
+use anchor_lang::solana_program::incinerator;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    default_account_state::DefaultAccountState, permanent_delegate::PermanentDelegate, BaseStateWithExtensions,
+    StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::AccountState;
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::constants::{
+    BURN_DELEGATE_SEED, ESCROW_SEED, EVENT_LEVEL_FULL, MAX_BPS, MAX_EVENT_NAME_LEN, METADATA_PROGRAM_ID,
+    MINT_BURN_LIMIT_SEED, PROTECTED_MINTS, SPL_BURN_TAG, SPL_CLOSE_ACCOUNT_TAG,
+};
+use crate::errors::BurnerError;
+use crate::events::TokenBurned;
+use crate::instructions::close::{
+    record_reclaimed_rent, require_close_authority, require_not_program_account, require_unique_close,
+};
+use crate::logs;
+use crate::state::{
+    BurnerState, EpochStats, MintBurnLimit, ReclaimHistory, ReclaimRecord, ShardCounter, VaultAccount,
+};
+
+pub(crate) fn burn_and_close_token_account(
+    ctx: Context<BurnAndCloseTokenAccount>,
+    force: bool,
+    deadline: i64,
+    strict_empty: bool,
+) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    // A signed-but-delayed transaction must not land after the signer's cutoff
+    let now = ctx.accounts.state.now()?;
+    require!(deadline == 0 || now <= deadline, BurnerError::DeadlineExceeded);
+
+    require_not_program_account(
+        &ctx.accounts.token_account.key(),
+        &[ctx.accounts.vault.key(), ctx.accounts.state.key(), ctx.accounts.state.treasury],
+    )?;
+    require_unique_close(&ctx.accounts.state, ctx.accounts.instructions.as_ref(), &ctx.accounts.token_account.key())?;
+
+    let token_account = &ctx.accounts.token_account;
+    let user = &ctx.accounts.user;
+    
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+    require_close_authority(token_account.close_authority, &user.key())?;
+    
+    let token_amount = token_account.amount;
+    // Close-only intent: a balance is a mistake to surface, not something to burn
+    require!(!strict_empty || token_amount == 0, BurnerError::AccountNotEmpty);
+    
+    #[cfg(not(feature = "compact-logs"))]
+    msg!(
+        "Burning and closing token account - Mint: {}, Amount: {}, Owner: {}",
+        token_account.mint,
+        token_amount,
+        token_account.owner
+    );
+    
+    // Wrapped SOL and the like are never junk; only an explicit force gets past
+    if PROTECTED_MINTS.contains(&token_account.mint) {
+        require!(force, BurnerError::ProtectedSystemMint);
+        msg!("Protected mint {} allowed with force", token_account.mint);
+    }
+
+    // Safety net against one burn wiping out a large share of supply
+    if force {
+        msg!("Supply guardrail bypassed with force");
+    } else {
+        ctx.accounts.state.check_supply_guardrail(token_amount, ctx.accounts.mint.supply)?;
+    }
+
+    // Strict-mode vaults opt out of mints whose issuer can still freeze holders
+    if ctx.accounts.vault.strict_mode && ctx.accounts.mint.freeze_authority.is_some() {
+        require!(force, BurnerError::FreezableMint);
+        msg!("Freezable mint allowed with force");
+    }
+    
+    // Token-2022 mints with a frozen DefaultAccountState open every account frozen
+    if ctx.accounts.token_account.is_frozen() {
+        thaw_for_burn(ctx.accounts)?;
+    }
+    
+    // Enforce the global daily burn limit before touching any tokens
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+    
+    collect_token_fee(ctx.accounts)?;
+    
+    // Only burn if there are tokens to burn
+    if token_amount > 0 {
+        // Create CPI context for burning tokens
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        
+        // Burn all tokens in the account
+        token_interface::burn(burn_ctx, token_amount)?;
+        
+        msg!("Burned {} tokens from mint {}", token_amount, token_account.mint);
+    } else {
+        msg!("No tokens to burn, proceeding to close account");
+    }
+    
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+    
+    // Create CPI context for closing the token account
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    
+    // Close the token account - rent goes to vault
+    token_interface::close_account(close_ctx)?;
+    
+    // Update vault lamports collected (optional tracking)
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, token_amount)?;
+
+    if let Some(shard) = ctx.accounts.shard.as_mut() {
+        shard.record(token_amount, 1, rent_lamports)?;
+    }
+    if let Some(epoch_stats) = ctx.accounts.epoch_stats.as_mut() {
+        epoch_stats.record(ctx.accounts.state.epoch()?, ctx.bumps.epoch_stats.unwrap_or_default(), token_amount, 1)?;
+    }
+    if let Some(history) = ctx.accounts.history.as_mut() {
+        history.push(ReclaimRecord {
+            mint: ctx.accounts.token_account.mint,
+            amount: token_amount,
+            rent: rent_lamports,
+            timestamp: now,
+        });
+    }
+    
+    logs::burned_and_closed(
+        &ctx.accounts.token_account.mint,
+        &ctx.accounts.token_account.owner,
+        token_amount,
+        rent_lamports,
+    );
+    if ctx.accounts.state.emits(EVENT_LEVEL_FULL) {
+        let name = match ctx.accounts.metadata.as_ref() {
+            Some(metadata) => metadata_name(metadata, &ctx.accounts.token_account.mint)?,
+            None => String::new(),
+        };
+        let event = TokenBurned {
+            user: ctx.accounts.user.key(),
+            token_account: ctx.accounts.token_account.key(),
+            mint: ctx.accounts.token_account.mint,
+            amount: token_amount,
+            rent: rent_lamports,
+            name,
+        };
+        // a self-CPI lands in the inner instructions, which RPCs don't truncate like logs
+        #[cfg(feature = "event-cpi")]
+        emit_cpi!(event);
+        #[cfg(not(feature = "event-cpi"))]
+        emit!(event);
+    }
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+/// Thaws a frozen account so it can be burned and closed, which takes the mint's
+/// freeze authority co-signing as `freeze_authority`. Without that signature the
+/// error says why the account is frozen: a `DefaultAccountState` mint freezes
+/// every new account, anything else was frozen by the issuer.
+fn thaw_for_burn(accounts: &BurnAndCloseTokenAccount) -> Result<()> {
+    let default_frozen = mint_defaults_frozen(&accounts.mint.to_account_info())?;
+    let Some(mint_freeze_authority) = Option::<Pubkey>::from(accounts.mint.freeze_authority) else {
+        return err!(BurnerError::FrozenWithoutFreezeAuthority);
+    };
+    let Some(freeze_authority) = accounts.freeze_authority.as_ref() else {
+        return if default_frozen {
+            err!(BurnerError::DefaultFrozenAccount)
+        } else {
+            err!(BurnerError::AccountFrozen)
+        };
+    };
+    require_keys_eq!(freeze_authority.key(), mint_freeze_authority, BurnerError::NotFreezeAuthority);
+
+    let thaw_accounts = token_interface::ThawAccount {
+        account: accounts.token_account.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+        authority: freeze_authority.to_account_info(),
+    };
+    let thaw_ctx = CpiContext::new(accounts.token_program.to_account_info(), thaw_accounts);
+    token_interface::thaw_account(thaw_ctx)?;
+
+    msg!("Thawed {} account before burning", if default_frozen { "default-frozen" } else { "frozen" });
+    Ok(())
+}
+
+/// Whether `mint` is a Token-2022 mint whose `DefaultAccountState` extension
+/// creates accounts frozen. Always false for SPL Token mints.
+fn mint_defaults_frozen(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != Token2022::id() {
+        return Ok(false);
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint
+        .get_extension::<DefaultAccountState>()
+        .is_ok_and(|extension| extension.state == AccountState::Frozen as u8))
+}
+
+/// Pays the configured token fee from the user's `fee_source` into the fee
+/// treasury, ahead of the burn. A no-op while `token_fee_amount` is 0; once it
+/// is set, every `fee_*` account must be passed, the context having already
+/// pinned `fee_mint` and `fee_treasury` to the configured ones.
+fn collect_token_fee(accounts: &BurnAndCloseTokenAccount) -> Result<()> {
+    let fee = accounts.state.token_fee_amount;
+    if fee == 0 || accounts.state.fees_waived()? {
+        return Ok(());
+    }
+    let (Some(fee_source), Some(fee_mint), Some(fee_treasury), Some(fee_token_program)) = (
+        accounts.fee_source.as_ref(),
+        accounts.fee_mint.as_ref(),
+        accounts.fee_treasury.as_ref(),
+        accounts.fee_token_program.as_ref(),
+    ) else {
+        return err!(BurnerError::TokenFeeAccountsMissing);
+    };
+    require!(fee_source.owner == accounts.user.key(), BurnerError::UnauthorizedAccount);
+    // Only the program that owns the fee mint may move it, not just any token interface
+    require_keys_eq!(
+        fee_token_program.key(),
+        *fee_mint.to_account_info().owner,
+        BurnerError::InvalidTokenFeeAccount
+    );
+    // The fee must not come out of the balance that is about to be burned
+    require_keys_neq!(fee_source.key(), accounts.token_account.key(), BurnerError::InvalidTokenFeeAccount);
+
+    let transfer_accounts = token_interface::TransferChecked {
+        from: fee_source.to_account_info(),
+        mint: fee_mint.to_account_info(),
+        to: fee_treasury.to_account_info(),
+        authority: accounts.user.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(fee_token_program.to_account_info(), transfer_accounts);
+    token_interface::transfer_checked(transfer_ctx, fee, fee_mint.decimals)?;
+
+    msg!("Token fee of {} paid to {}", fee, fee_treasury.key());
+    Ok(())
+}
+
+// Metaplex `Key::MetadataV1`, the first byte of every metadata account
+const METADATA_KEY_V1: u8 = 4;
+
+/// The token name out of a Metaplex metadata account of `mint`, cut to
+/// `MAX_EVENT_NAME_LEN` bytes. The context already checked the account is owned by
+/// the metadata program; the mint field is checked here instead of re-deriving the
+/// PDA, which the metadata program guarantees is the only such account per mint.
+/// Layout: key `u8`, update authority, mint, then the name as a Borsh string,
+/// NUL-padded to 32 bytes.
+fn metadata_name(metadata: &AccountInfo, mint: &Pubkey) -> Result<String> {
+    let data = metadata.try_borrow_data()?;
+    require!(data.len() >= 69 && data[0] == METADATA_KEY_V1, BurnerError::InvalidMetadataAccount);
+    require!(data[33..65] == mint.to_bytes(), BurnerError::InvalidMetadataAccount);
+
+    let len = u32::from_le_bytes(data[65..69].try_into().unwrap()) as usize;
+    let raw = data.get(69..69 + len).ok_or(BurnerError::InvalidMetadataAccount)?;
+    let name = String::from_utf8_lossy(raw);
+    let name = name.trim_end_matches('\0');
+    let mut end = name.len().min(MAX_EVENT_NAME_LEN);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    Ok(name[..end].to_owned())
+}
+
+pub(crate) fn burn_bps(ctx: Context<BurnBps>, bps: u16) -> Result<()> {
+    let token_account = &ctx.accounts.token_account;
+    let user = &ctx.accounts.user;
+
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+
+    // 0 would be a silent no-op on any balance, so it's a caller error rather than a burn
+    require!(bps > 0 && bps <= MAX_BPS, BurnerError::InvalidBps);
+
+    // amount * bps / 10000 in u128 so the product can't overflow
+    let burn_amount = (token_account.amount as u128)
+        .checked_mul(bps as u128)
+        .and_then(|v| v.checked_div(MAX_BPS as u128))
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or(BurnerError::MathOverflow)?;
+
+    // Zero balance, or a share that rounds down to nothing: succeed without a CPI,
+    // the way the burn-and-close variants skip the burn for an empty account
+    if burn_amount == 0 {
+        msg!("Nothing to burn: {} bps of {} tokens is 0", bps, token_account.amount);
+        return Ok(());
+    }
+
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(burn_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, burn_amount, now)?;
+
+    let burn_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+    burn(burn_ctx, burn_amount)?;
+
+    msg!(
+        "Burned {} bps of balance ({} tokens) from mint {}",
+        bps,
+        burn_amount,
+        token_account.mint
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+// Return-data payload of `burn_to_supply`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SupplyBurnOutcome {
+    pub burned: u64,        // raw tokens this call burned
+    pub supply_after: u64,  // mint supply once the burn landed
+    pub shortfall: u64,     // supply still above target_supply, 0 once it's reached
+}
+
+pub(crate) fn burn_to_supply(ctx: Context<BurnToSupply>, target_supply: u64) -> Result<SupplyBurnOutcome> {
+    let token_account = &ctx.accounts.token_account;
+
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == ctx.accounts.user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let supply = ctx.accounts.mint.supply;
+    let needed = supply.saturating_sub(target_supply);
+    // Clamped to the balance; whatever is left over is for the next account to burn
+    let burn_amount = needed.min(token_account.amount);
+    let shortfall = needed - burn_amount;
+
+    if burn_amount > 0 {
+        let now = ctx.accounts.state.now()?;
+        ctx.accounts.state.record_burn(burn_amount, now)?;
+        MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, burn_amount, now)?;
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        burn(burn_ctx, burn_amount)?;
+    }
+
+    let supply_after = supply - burn_amount;
+    if shortfall > 0 {
+        msg!(
+            "Burned {} tokens, supply {} is still {} above target {}",
+            burn_amount,
+            supply_after,
+            shortfall,
+            target_supply
+        );
+    } else {
+        msg!("Burned {} tokens, supply {} is at or below target {}", burn_amount, supply_after, target_supply);
+    }
+    ctx.accounts.state.end_processing();
+    Ok(SupplyBurnOutcome {
+        burned: burn_amount,
+        supply_after,
+        shortfall,
+    })
+}
+
+pub(crate) fn burn_and_close_pda_owned(
+    ctx: Context<BurnAndClosePdaOwned>,
+    seeds: Vec<Vec<u8>>,
+    bump: u8,
+) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let token_account = &ctx.accounts.token_account;
+    let user_key = ctx.accounts.user.key();
+    let bump_seed = [bump];
+
+    // Scope the PDA to the signing user so nobody else can drain it
+    let mut signer_seeds: Vec<&[u8]> = Vec::with_capacity(seeds.len() + 3);
+    signer_seeds.push(ESCROW_SEED);
+    signer_seeds.push(user_key.as_ref());
+    signer_seeds.extend(seeds.iter().map(|s| s.as_slice()));
+    signer_seeds.push(&bump_seed);
+
+    // Security: the reconstructed PDA must be the token account's owner
+    let pda = Pubkey::create_program_address(&signer_seeds, ctx.program_id)
+        .map_err(|_| error!(BurnerError::InvalidPdaSeeds))?;
+    require_keys_eq!(pda, token_account.owner, BurnerError::InvalidPdaSeeds);
+    require_close_authority(token_account.close_authority, &pda)?;
+
+    let token_amount = token_account.amount;
+    msg!(
+        "Burning and closing PDA-owned token account - Mint: {}, Amount: {}, Owner: {}",
+        token_account.mint,
+        token_amount,
+        pda
+    );
+
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    let signer: &[&[&[u8]]] = &[&signer_seeds];
+
+    if token_amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.pda_owner.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            burn_accounts,
+            signer,
+        );
+        token_interface::burn(burn_ctx, token_amount)?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.pda_owner.to_account_info(),
+    };
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_accounts,
+        signer,
+    );
+    token_interface::close_account(close_ctx)?;
+
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, token_amount)?;
+
+    msg!(
+        "Burned {} tokens and closed PDA-owned account, {} lamports sent to vault",
+        token_amount,
+        rent_lamports
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn permanent_delegate_burn(ctx: Context<PermanentDelegateBurn>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let delegate_key = ctx.accounts.delegate.key();
+
+    // Security: the signer must be the delegate named in the mint's extension
+    let permanent_delegate = {
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        let extension = mint
+            .get_extension::<PermanentDelegate>()
+            .map_err(|_| BurnerError::NotPermanentDelegate)?;
+        Option::<Pubkey>::from(extension.delegate)
+    };
+    require!(
+        permanent_delegate == Some(delegate_key),
+        BurnerError::NotPermanentDelegate
+    );
+
+    let token_account = &ctx.accounts.token_account;
+    let token_amount = token_account.amount;
+    msg!(
+        "Permanent delegate burn - Mint: {}, Amount: {}, Owner: {}",
+        token_account.mint,
+        token_amount,
+        token_account.owner
+    );
+
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if token_amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.delegate.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token_interface::burn(burn_ctx, token_amount)?;
+    }
+
+    // Permanent delegation covers burns, not closes: closing also needs the
+    // delegate to be the account's close authority. Rent goes back to the holder.
+    if token_account.close_authority == COption::Some(delegate_key) {
+        let close_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.delegate.to_account_info(),
+        };
+        let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+        token_interface::close_account(close_ctx)?;
+        msg!("Burned {} tokens and closed the account, rent returned to owner", token_amount);
+    } else {
+        msg!("Burned {} tokens; account left open, delegate is not its close authority", token_amount);
+    }
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn dispose_to_incinerator(ctx: Context<DisposeToIncinerator>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let token_account = &ctx.accounts.token_account;
+
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == ctx.accounts.user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+    require_close_authority(token_account.close_authority, &ctx.accounts.user.key())?;
+
+    let token_amount = token_account.amount;
+
+    // Tokens sent to the incinerator are gone for good, so they count as burned
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if token_amount > 0 {
+        let transfer_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.incinerator_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts);
+        token_interface::transfer_checked(transfer_ctx, token_amount, ctx.accounts.mint.decimals)?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    token_interface::close_account(close_ctx)?;
+
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, token_amount)?;
+
+    msg!(
+        "Sent {} tokens of mint {} to the incinerator and closed the account, {} lamports sent to vault",
+        token_amount,
+        ctx.accounts.mint.key(),
+        rent_lamports
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn issuer_retire_account(ctx: Context<IssuerRetireAccount>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let issuer_key = ctx.accounts.issuer.key();
+    require!(
+        ctx.accounts.mint.freeze_authority == COption::Some(issuer_key),
+        BurnerError::NotFreezeAuthority
+    );
+    // Freezing grants no say over closes, the holder must have delegated that up front
+    require!(
+        ctx.accounts.token_account.close_authority == COption::Some(issuer_key),
+        BurnerError::InvalidCloseAuthority
+    );
+
+    let token_amount = ctx.accounts.token_account.amount;
+    msg!(
+        "Issuer retiring token account - Mint: {}, Amount: {}, Owner: {}",
+        ctx.accounts.token_account.mint,
+        token_amount,
+        ctx.accounts.token_account.owner
+    );
+
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if ctx.accounts.token_account.is_frozen() {
+        let thaw_accounts = token_interface::ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.issuer.to_account_info(),
+        };
+        let thaw_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), thaw_accounts);
+        token_interface::thaw_account(thaw_ctx)?;
+    }
+
+    // The token program checks the issuer holds burn rights: an approved delegate
+    // for the full balance, or the permanent delegate on Token-2022
+    if token_amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.issuer.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token_interface::burn(burn_ctx, token_amount)?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+
+    // Non-custodial: the rent lands in the holder's vault, never with the issuer
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.issuer.to_account_info(),
+    };
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    token_interface::close_account(close_ctx)?;
+
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, token_amount)?;
+
+    msg!(
+        "Retired {} tokens and closed the account, {} lamports sent to the holder's vault",
+        token_amount,
+        rent_lamports
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn burn_with_receipt_init(ctx: Context<BurnWithReceiptInit>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    require_not_program_account(
+        &ctx.accounts.token_account.key(),
+        &[ctx.accounts.vault.key(), ctx.accounts.state.key(), ctx.accounts.state.treasury],
+    )?;
+    require_close_authority(ctx.accounts.token_account.close_authority, &ctx.accounts.user.key())?;
+
+    let token_amount = ctx.accounts.token_account.amount;
+    ctx.accounts.state.check_supply_guardrail(token_amount, ctx.accounts.mint.supply)?;
+    require!(
+        !(ctx.accounts.vault.strict_mode && ctx.accounts.mint.freeze_authority.is_some()),
+        BurnerError::FreezableMint
+    );
+
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if token_amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token_interface::burn(burn_ctx, token_amount)?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    token_interface::close_account(close_ctx)?;
+
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, token_amount)?;
+
+    // One receipt token per closed account, signed for by the state PDA
+    let state_seeds: &[&[u8]] = &[b"state", &[ctx.accounts.state.state_bump]];
+    let signer_seeds = &[state_seeds];
+    let mint_accounts = MintTo {
+        mint: ctx.accounts.receipt_mint.to_account_info(),
+        to: ctx.accounts.receipt_account.to_account_info(),
+        authority: ctx.accounts.state.to_account_info(),
+    };
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.receipt_token_program.to_account_info(),
+        mint_accounts,
+        signer_seeds,
+    );
+    mint_to(mint_ctx, 1)?;
+
+    logs::burned_and_closed(
+        &ctx.accounts.token_account.mint,
+        &ctx.accounts.token_account.owner,
+        token_amount,
+        rent_lamports,
+    );
+    msg!("Receipt minted to {}", ctx.accounts.receipt_account.key());
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn recycle_token_account(ctx: Context<RecycleTokenAccount>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    require_close_authority(ctx.accounts.token_account.close_authority, &ctx.accounts.user.key())?;
+
+    let token_amount = ctx.accounts.token_account.amount;
+    ctx.accounts.state.check_supply_guardrail(token_amount, ctx.accounts.mint.supply)?;
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if token_amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token_interface::burn(burn_ctx, token_amount)?;
+    }
+
+    // The rent goes back to the wallet that pays for the new ATA right after,
+    // instead of the vault, so the two cancel out
+    let user_lamports_before = ctx.accounts.user.lamports();
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.user.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    token_interface::close_account(close_ctx)?;
+    let rent_reclaimed = ctx.accounts.user.lamports().saturating_sub(user_lamports_before);
+
+    // The token program hands a closed account back to the system program with no
+    // data, so the ATA program can create it again at the same address
+    let create_accounts = associated_token::Create {
+        payer: ctx.accounts.user.to_account_info(),
+        associated_token: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let create_ctx = CpiContext::new(ctx.accounts.associated_token_program.to_account_info(), create_accounts);
+    associated_token::create(create_ctx)?;
+    let rent_paid = ctx.accounts.token_account.to_account_info().lamports();
+
+    msg!(
+        "Recycled token account {} after burning {} tokens: {} lamports reclaimed, {} paid for the new account",
+        ctx.accounts.token_account.key(),
+        token_amount,
+        rent_reclaimed,
+        rent_paid
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn burn_close_any_token_program(ctx: Context<BurnCloseAnyTokenProgram>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let token_program_id = ctx.accounts.token_program.key();
+    let user_key = ctx.accounts.user.key();
+    let token_account_info = ctx.accounts.token_account.to_account_info();
+    let mint_info = ctx.accounts.mint.to_account_info();
+
+    // The typed contexts can't parse an account owned by a fork, so check it by hand:
+    // owned by the allowlisted program, SPL layout (the base state Token-2022 also uses)
+    require_keys_eq!(*token_account_info.owner, token_program_id, BurnerError::NotATokenAccount);
+    let token_account = {
+        let data = token_account_info.try_borrow_data()?;
+        StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Account>::unpack(&data)
+            .map_err(|_| BurnerError::NotATokenAccount)?
+            .base
+    };
+    require_keys_eq!(token_account.owner, user_key, BurnerError::UnauthorizedAccount);
+    require_keys_eq!(token_account.mint, mint_info.key(), BurnerError::MintMismatch);
+    require!(!token_account.is_frozen(), BurnerError::AccountFrozen);
+    require_close_authority(token_account.close_authority, &user_key)?;
+    require_not_program_account(
+        &token_account_info.key(),
+        &[ctx.accounts.vault.key(), ctx.accounts.state.key(), ctx.accounts.state.treasury],
+    )?;
+
+    let token_amount = token_account.amount;
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    // Raw SPL instructions: spl_token's builders refuse any program id but their own
+    if token_amount > 0 {
+        let mut data = vec![SPL_BURN_TAG];
+        data.extend_from_slice(&token_amount.to_le_bytes());
+        let burn_ix = Instruction {
+            program_id: token_program_id,
+            accounts: vec![
+                AccountMeta::new(token_account_info.key(), false),
+                AccountMeta::new(mint_info.key(), false),
+                AccountMeta::new_readonly(user_key, true),
+            ],
+            data,
+        };
+        invoke(
+            &burn_ix,
+            &[token_account_info.clone(), mint_info.clone(), ctx.accounts.user.to_account_info()],
+        )?;
+    }
+
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_lamports_before = vault_info.lamports();
+    let close_ix = Instruction {
+        program_id: token_program_id,
+        accounts: vec![
+            AccountMeta::new(token_account_info.key(), false),
+            AccountMeta::new(vault_info.key(), false),
+            AccountMeta::new_readonly(user_key, true),
+        ],
+        data: vec![SPL_CLOSE_ACCOUNT_TAG],
+    };
+    invoke(
+        &close_ix,
+        &[token_account_info.clone(), vault_info, ctx.accounts.user.to_account_info()],
+    )?;
+
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, token_amount)?;
+
+    logs::burned_and_closed(&token_account.mint, &user_key, token_amount, rent_lamports);
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn burn_delegated_capped(ctx: Context<BurnDelegatedCapped>, amount: u64) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let token_account = &ctx.accounts.token_account;
+    let delegate_key = ctx.accounts.burn_delegate.key();
+
+    // The owner's own `approve` is the only authorization, and its amount the cap
+    require!(
+        token_account.delegate == COption::Some(delegate_key),
+        BurnerError::BurnDelegateNotApproved
+    );
+    require!(amount <= token_account.delegated_amount, BurnerError::DelegationExceeded);
+    let remaining = token_account.delegated_amount - amount;
+
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, amount, now)?;
+
+    let owner = ctx.accounts.token_account.owner;
+    let delegate_seeds: &[&[u8]] = &[BURN_DELEGATE_SEED, owner.as_ref(), &[ctx.bumps.burn_delegate]];
+    let signer_seeds = &[delegate_seeds];
+    let burn_accounts = token_interface::Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.burn_delegate.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        burn_accounts,
+        signer_seeds,
+    );
+    // The token program decrements delegated_amount itself, and drops the delegate at 0
+    token_interface::burn(burn_ctx, amount)?;
+
+    msg!(
+        "Delegated burn of {} tokens from mint {} for {}, {} left in the allowance",
+        amount,
+        ctx.accounts.token_account.mint,
+        owner,
+        remaining
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+// Account context for `burn_and_close_token_account`
+// Designed to work efficiently with ALTs for batch operations
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct BurnAndCloseTokenAccount<'info> {
+    /// User who owns the token account
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// SPL Token or Token-2022 account to burn and close
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    /// The mint of the token (required for burning)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    /// User's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+    
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+    
+    /// Optional shard counter to book this burn on; the client picks the index
+    #[account(mut, seeds = [b"shard".as_ref(), &[shard.index]], bump = shard.bump)]
+    pub shard: Option<Account<'info, ShardCounter>>,
+    
+    /// Optional rollup for the current epoch, created by the first burn that passes it
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + EpochStats::INIT_SPACE,
+        seeds = [b"epoch".as_ref(), &state.epoch()?.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Option<Account<'info, EpochStats>>,
+    
+    /// Optional `ReclaimHistory` of the user, gets a record of this close
+    #[account(mut, seeds = [b"history", user.key().as_ref()], bump = history.bump)]
+    pub history: Option<Box<Account<'info, ReclaimHistory>>>,
+    
+    /// Optional Metaplex metadata of the mint, source of the name in `TokenBurned`
+    /// CHECK: owner checked here, mint field checked in `metadata_name`
+    #[account(owner = METADATA_PROGRAM_ID @ BurnerError::InvalidMetadataAccount)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// User's token account of the fee mint, pays the token fee
+    #[account(mut)]
+    pub fee_source: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+    
+    /// Mint of the token fee, must be `state.token_fee_mint`
+    #[account(address = state.token_fee_mint @ BurnerError::InvalidTokenFeeAccount)]
+    pub fee_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+    
+    /// Receives the token fee, must be `state.token_fee_treasury`
+    #[account(mut, address = state.token_fee_treasury @ BurnerError::InvalidTokenFeeAccount)]
+    pub fee_treasury: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+    
+    /// Token program owning `fee_mint`, which may differ from `token_program`
+    pub fee_token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Optional freeze authority of the mint, co-signs to thaw a frozen account first;
+    /// checked in the handler
+    pub freeze_authority: Option<Signer<'info>>,
+    
+    /// CHECK: instructions sysvar, required while `state.check_duplicate_closes` is on
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// System program (required by `init_if_needed` to create `epoch_stats`)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `burn_bps`
+#[derive(Accounts)]
+pub struct BurnBps<'info> {
+    /// User who owns the token account
+    pub user: Signer<'info>,
+
+    /// SPL Token account to burn from (stays open)
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// The mint of the token (required for burning)
+    #[account(mut)]
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+}
+
+// Account context for `burn_to_supply`
+#[derive(Accounts)]
+pub struct BurnToSupply<'info> {
+    /// User who owns the token account
+    pub user: Signer<'info>,
+
+    /// SPL Token account to burn from (stays open)
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Mint whose supply is targeted; its `supply` is read before the burn
+    #[account(mut, constraint = mint.key() == token_account.mint @ BurnerError::MintMismatch)]
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+}
+
+// Account context for `burn_and_close_pda_owned`
+#[derive(Accounts)]
+pub struct BurnAndClosePdaOwned<'info> {
+    /// User whose key scopes the owner PDA; receives the rent in their vault
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: program PDA owning the token account; verified against the seeds in the handler
+    pub pda_owner: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 account owned by `pda_owner`
+    #[account(
+        mut,
+        constraint = token_account.owner == pda_owner.key() @ BurnerError::UnauthorizedAccount
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The mint of the token (required for burning)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// User's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Account context for `permanent_delegate_burn`
+#[derive(Accounts)]
+pub struct PermanentDelegateBurn<'info> {
+    /// Permanent delegate of the mint; checked against the extension in the handler
+    pub delegate: Signer<'info>,
+
+    /// Token-2022 account of any holder of `mint`
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ BurnerError::MintMismatch
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token-2022 mint carrying the PermanentDelegate extension
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// CHECK: only receives the rent if the account gets closed; must be the holder
+    #[account(mut, address = token_account.owner @ BurnerError::InvalidOwner)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// Token-2022 program, the only one with the extension
+    pub token_program: Program<'info, Token2022>,
+}
+
+// Account context for `dispose_to_incinerator`
+#[derive(Accounts)]
+pub struct DisposeToIncinerator<'info> {
+    /// User who owns the token account; pays for the incinerator's ATA if it's missing
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token or Token-2022 account to empty and close
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ BurnerError::MintMismatch
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The mint of the token (required for `transfer_checked`)
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// CHECK: the canonical incinerator, nobody holds its key
+    #[account(address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    /// The incinerator's associated token account for `mint`
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = incinerator,
+        associated_token::token_program = token_program
+    )]
+    pub incinerator_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// User's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, books the disposal against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated Token program (creates `incinerator_token_account`)
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program (required by `init_if_needed`)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `issuer_retire_account`
+#[derive(Accounts)]
+pub struct IssuerRetireAccount<'info> {
+    /// Freeze authority of `mint`; checked in the handler
+    pub issuer: Signer<'info>,
+
+    /// Any holder's account of `mint`, frozen or not
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ BurnerError::MintMismatch
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The mint whose freeze authority is `issuer`
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// The holder's vault PDA, receives the rent
+    #[account(
+        mut,
+        seeds = [b"vault", token_account.owner.as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == token_account.owner @ BurnerError::VaultOwnerMismatch
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Account context for `burn_with_receipt_init`
+#[derive(Accounts)]
+pub struct BurnWithReceiptInit<'info> {
+    /// User who owns the token account, pays for the receipt ATA if it's new
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token or Token-2022 account to burn and close
+    #[account(
+        mut,
+        constraint = token_account.owner == user.key() @ BurnerError::UnauthorizedAccount,
+        constraint = token_account.mint == mint.key() @ BurnerError::MintMismatch
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The mint of the token (required for burning)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// User's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, books the burn and signs the receipt mint
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// Receipt mint whose mint authority is the state PDA
+    #[account(
+        mut,
+        constraint = receipt_mint.mint_authority == COption::Some(state.key()) @ BurnerError::InvalidMintAuthority
+    )]
+    pub receipt_mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// The user's receipt ATA, created on the first receipt and reused after
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = user,
+        associated_token::token_program = receipt_token_program
+    )]
+    pub receipt_account: Account<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program owning the receipt mint
+    pub receipt_token_program: Program<'info, Token>,
+
+    /// Associated Token program (creates `receipt_account`)
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program (required by `init_if_needed`)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `recycle_token_account`
+#[derive(Accounts)]
+pub struct RecycleTokenAccount<'info> {
+    /// Owner of the account; gets the old rent back and pays for the new account
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The user's ATA for `mint`, burned, closed and recreated at the same address
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The mint of the token (required for burning)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated Token program (recreates `token_account`)
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program (funds the new account)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `burn_close_any_token_program`
+#[derive(Accounts)]
+pub struct BurnCloseAnyTokenProgram<'info> {
+    /// User who owns the token account
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: token account of `token_program`; owner and SPL layout checked in the handler
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// CHECK: must be the token account's mint, checked in the handler; the token
+    /// program validates it on burn
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// User's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, holds the allowlist and books the burn
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// CHECK: any program on `state.token_program_allowlist` exposing the SPL interface
+    #[account(
+        executable,
+        constraint = state.allows_token_program(&token_program.key()) @ BurnerError::TokenProgramNotAllowed
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+// Account context for `burn_delegated_capped`
+// No owner signature: the owner's `approve` of the burn delegate PDA is the authorization.
+#[derive(Accounts)]
+pub struct BurnDelegatedCapped<'info> {
+    /// SPL Token or Token-2022 account that approved the burn delegate (stays open)
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The mint of the token (required for burning)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// CHECK: signing-only PDA, ["burn_delegate", owner]; the delegate the owner approved
+    #[account(seeds = [BURN_DELEGATE_SEED, token_account.owner.as_ref()], bump)]
+    pub burn_delegate: UncheckedAccount<'info>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
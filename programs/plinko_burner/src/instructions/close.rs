@@ -0,0 +1,513 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::sysvar::instructions::{self as sysvar_instructions, load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, spl_token::native_mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::errors::BurnerError;
+use crate::events::WsolUnwrapped;
+use crate::logs;
+use crate::state::{BurnerState, ReclaimHistory, ReclaimRecord, VaultAccount};
+
+pub(crate) fn close_token_account(ctx: Context<CloseTokenAccount>) -> Result<()> {
+    let accounts = ctx.accounts;
+    require_not_program_account(
+        &accounts.token_account.key(),
+        &[accounts.vault.key(), accounts.state.key(), accounts.state.treasury],
+    )?;
+    require_unique_close(&accounts.state, accounts.instructions.as_ref(), &accounts.token_account.key())?;
+    let mint = accounts.token_account.mint;
+    let dust = accounts.token_account.amount;
+    let rent = close_empty_account(
+        &accounts.user,
+        &mut accounts.token_account,
+        &mut accounts.vault,
+        &accounts.token_program,
+        accounts.state.dust_threshold,
+        accounts.mint.as_ref(),
+    )?;
+    if let Some(history) = accounts.history.as_mut() {
+        history.push(ReclaimRecord { mint, amount: dust, rent, timestamp: accounts.state.now()? });
+    }
+    auto_withdraw_excess(accounts)
+}
+
+/// Moves the vault's usable lamports above `auto_withdraw_threshold` to the owner's
+/// wallet. Only when the owner signed the close, since a close authority's signer
+/// isn't the owner's wallet, and never around a withdrawal guard: with a co-signer
+/// required, the cooldown running or the excess below `min_withdraw_lamports`, it
+/// leaves the lamports where they are instead of failing the close.
+fn auto_withdraw_excess(accounts: &mut CloseTokenAccount) -> Result<()> {
+    let threshold = accounts.vault.auto_withdraw_threshold;
+    if threshold == 0 || accounts.user.key() != accounts.vault.owner {
+        return Ok(());
+    }
+    let vault_info = accounts.vault.to_account_info();
+    let excess = accounts.vault.usable_lamports(&vault_info)?.saturating_sub(threshold);
+    if excess == 0 {
+        return Ok(());
+    }
+    if accounts.vault.required_cosigner.is_some() || excess < accounts.state.min_withdraw_lamports {
+        msg!("Auto-withdraw of {} lamports skipped", excess);
+        return Ok(());
+    }
+    let now = accounts.state.now()?;
+    if accounts.vault.record_withdrawal(accounts.state.withdraw_cooldown_secs, now).is_err() {
+        msg!("Auto-withdraw of {} lamports skipped, withdraw cooldown active", excess);
+        return Ok(());
+    }
+
+    **vault_info.try_borrow_mut_lamports()? -= excess;
+    **accounts.user.to_account_info().try_borrow_mut_lamports()? += excess;
+    msg!("Auto-withdrew {} lamports to the owner", excess);
+    Ok(())
+}
+
+pub(crate) fn close_token_account_lean(ctx: Context<CloseTokenAccountLean>, _vault_bump: u8) -> Result<()> {
+    // `_vault_bump` is consumed by the seeds constraint
+    // The token program enforces the close authority, but not emptiness: closing a
+    // native wSOL account with a balance sweeps the wrapped SOL along with the rent
+    let token_account = load_token_account(&ctx.accounts.token_account, &ctx.accounts.token_program.key())?;
+    require!(
+        token_account.owner == ctx.accounts.user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+    require!(token_account.amount == 0, BurnerError::AccountNotEmpty);
+
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts))
+}
+
+pub(crate) fn close_with_vault_init(ctx: Context<CloseWithVaultInit>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let user_key = accounts.user.key();
+
+    // First-ever use: init_if_needed just allocated a zeroed vault, fill it in
+    if accounts.vault.owner == Pubkey::default() {
+        require!(!accounts.state.vault_creation_paused, BurnerError::VaultCreationPaused);
+        let rent_floor = Rent::get()?.minimum_balance(accounts.vault.to_account_info().data_len());
+        let vault = &mut accounts.vault;
+        vault.set_inner(VaultAccount::init(user_key, ctx.bumps.vault, rent_floor));
+        msg!("Vault created for user: {}", vault.owner);
+    }
+    require!(accounts.vault.owner == user_key, BurnerError::InvalidOwner);
+    require_not_program_account(&accounts.token_account.key(), &[accounts.vault.key()])?;
+
+    close_empty_account(
+        &accounts.user,
+        &mut accounts.token_account,
+        &mut accounts.vault,
+        &accounts.token_program,
+        0,
+        None,
+    )?;
+    Ok(())
+}
+
+pub(crate) fn unwrap_and_close_wsol(ctx: Context<UnwrapAndCloseWsol>) -> Result<()> {
+    let token_account = &ctx.accounts.token_account;
+    let user_key = ctx.accounts.user.key();
+    require!(token_account.owner == user_key, BurnerError::UnauthorizedAccount);
+    require_close_authority(token_account.close_authority, &user_key)?;
+
+    // Closing a native account pays out every lamport on it. Split by the reserve
+    // rather than `amount`, which misses lamports sent in without a sync_native.
+    let rent_lamports = match token_account.is_native {
+        COption::Some(reserve) => reserve,
+        COption::None => return err!(BurnerError::NotWrappedSol),
+    };
+    let account_lamports = token_account.to_account_info().lamports();
+    let lamports_unwrapped = account_lamports.saturating_sub(rent_lamports);
+
+    // Not the vault: this is the user's own SOL, principal and rent alike
+    let close_accounts = token::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.user.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token::close_account(CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts))?;
+
+    if lamports_unwrapped > 0 {
+        msg!(
+            "Unwrapped {}.{:09} SOL plus {} lamports of rent to {}",
+            lamports_unwrapped / LAMPORTS_PER_SOL,
+            lamports_unwrapped % LAMPORTS_PER_SOL,
+            rent_lamports,
+            user_key
+        );
+    } else {
+        msg!("Empty wSOL account closed, {} lamports of rent to {}", rent_lamports, user_key);
+    }
+    // Not gated on `event_level`: unwrapping burns nothing and doesn't take the state
+    // account, so there's no level to read; the event is its one record of the payout
+    let event = WsolUnwrapped {
+        user: user_key,
+        token_account: ctx.accounts.token_account.key(),
+        lamports_unwrapped,
+        rent_lamports,
+    };
+    #[cfg(feature = "event-cpi")]
+    emit_cpi!(event);
+    #[cfg(not(feature = "event-cpi"))]
+    emit!(event);
+    Ok(())
+}
+
+pub(crate) fn close_token_account_to(ctx: Context<CloseTokenAccountTo>) -> Result<()> {
+    let token_account = &ctx.accounts.token_account;
+    let user_key = ctx.accounts.user.key();
+    require!(token_account.owner == user_key, BurnerError::UnauthorizedAccount);
+    require_close_authority(token_account.close_authority, &user_key)?;
+    require!(token_account.amount == 0, BurnerError::AccountNotEmpty);
+
+    // Non-custodial either way: the owner's vault or the owner's own wallet, nobody else
+    let destination = ctx.accounts.destination.to_account_info();
+    let (vault_key, _) = Pubkey::find_program_address(&[b"vault", user_key.as_ref()], ctx.program_id);
+    let to_vault = destination.key() == vault_key;
+    require!(
+        to_vault || destination.key() == user_key,
+        BurnerError::InvalidRentDestination
+    );
+    let mut vault = if to_vault {
+        require_keys_eq!(*destination.owner, *ctx.program_id, BurnerError::InvalidVaultAccount);
+        let vault = VaultAccount::try_deserialize(&mut &destination.try_borrow_data()?[..])
+            .map_err(|_| BurnerError::InvalidVaultAccount)?;
+        Some(vault)
+    } else {
+        None
+    };
+
+    let lamports_before = destination.lamports();
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: destination.clone(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts))?;
+    let rent_lamports = reclaimed_lamports(&destination, lamports_before)?;
+
+    // The vault came in unchecked, so its stats are written back by hand
+    if let Some(vault) = vault.as_mut() {
+        vault.lamports_collected = vault.lamports_collected.saturating_add(rent_lamports);
+        vault.record_closes(1, 0);
+        vault.try_serialize(&mut &mut destination.try_borrow_mut_data()?[..])?;
+    }
+
+    msg!(
+        "Token account closed, {} lamports sent to the owner's {}",
+        rent_lamports,
+        if to_vault { "vault" } else { "wallet" }
+    );
+    Ok(())
+}
+
+/// Lamports a close just moved into `destination`: its balance now minus
+/// `lamports_before`. Measured, not derived from a data length, so Token-2022
+/// accounts with extensions and any lamports above the rent reserve come out exact.
+pub(crate) fn reclaimed_lamports(destination: &AccountInfo, lamports_before: u64) -> Result<u64> {
+    destination
+        .lamports()
+        .checked_sub(lamports_before)
+        .ok_or_else(|| error!(BurnerError::MathOverflow))
+}
+
+/// Books the rent a close moved into `vault` on its `lamports_collected`, along
+/// with the `accounts_closed` token accounts and `tokens_burned` raw tokens it
+/// covers, and returns it. Read `lamports_before` right before the close CPI.
+pub(crate) fn record_reclaimed_rent(
+    vault: &mut Account<VaultAccount>,
+    lamports_before: u64,
+    accounts_closed: u64,
+    tokens_burned: u64,
+) -> Result<u64> {
+    let reclaimed = reclaimed_lamports(&vault.to_account_info(), lamports_before)?;
+    vault.lamports_collected = vault.lamports_collected.saturating_add(reclaimed);
+    vault.record_closes(accounts_closed, tokens_burned);
+    Ok(reclaimed)
+}
+
+/// With `state.check_duplicate_closes` on, scans the transaction through the
+/// instructions sysvar and fails with `DuplicateInTransaction` if any other
+/// `close_token_account` or `burn_and_close_token_account` targets the same
+/// token account (their second account). The second close would fail anyway,
+/// but with an opaque token-program error. Off by default: every call loads
+/// each instruction of the transaction.
+pub(crate) fn require_unique_close(
+    state: &BurnerState,
+    instructions: Option<&UncheckedAccount>,
+    token_account: &Pubkey,
+) -> Result<()> {
+    if !state.check_duplicate_closes {
+        return Ok(());
+    }
+    let instructions = instructions.ok_or(BurnerError::InstructionsSysvarMissing)?;
+    let current = load_current_index_checked(instructions)? as usize;
+
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions) {
+        let is_close = instruction.program_id == crate::ID
+            && (instruction.data.starts_with(crate::instruction::CloseTokenAccount::DISCRIMINATOR)
+                || instruction.data.starts_with(crate::instruction::BurnAndCloseTokenAccount::DISCRIMINATOR));
+        if index != current && is_close && instruction.accounts.get(1).is_some_and(|meta| meta.pubkey == *token_account) {
+            return err!(BurnerError::DuplicateInTransaction);
+        }
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Parses a token account passed as a bare `AccountInfo`, doing by hand what
+/// `InterfaceAccount<TokenAccount>` would: owned by `token_program` and of the
+/// SPL account layout, so mints, multisigs and foreign data all fail with
+/// `NotATokenAccount` before any CPI sees them.
+pub(crate) fn load_token_account(info: &AccountInfo, token_program: &Pubkey) -> Result<token_interface::TokenAccount> {
+    require_keys_eq!(*info.owner, *token_program, BurnerError::NotATokenAccount);
+    token_interface::TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])
+        .map_err(|_| BurnerError::NotATokenAccount.into())
+}
+
+/// Defence in depth against a client passing one of our own accounts as the
+/// account to close. The token-account types already reject program-owned data,
+/// so this only bites if a context ever loosens to an unchecked account.
+pub(crate) fn require_not_program_account(token_account: &Pubkey, protected: &[Pubkey]) -> Result<()> {
+    require!(!protected.contains(token_account), BurnerError::CannotCloseProgramAccount);
+    Ok(())
+}
+
+/// Once a close authority is set, the token program lets only that key close the
+/// account, not the owner. Checking up front turns its opaque owner-mismatch
+/// error into `CloseAuthorityConflict`.
+pub(crate) fn require_close_authority(close_authority: COption<Pubkey>, signer: &Pubkey) -> Result<()> {
+    if let COption::Some(close_authority) = close_authority {
+        require_keys_eq!(close_authority, *signer, BurnerError::CloseAuthorityConflict);
+    }
+    Ok(())
+}
+
+/// Shared body of the close instructions: checks the user may close the account
+/// and that it is empty, closes it into the owner's vault and tallies the rent.
+/// A balance of at most `dust_threshold` counts as empty and is burned first,
+/// which needs `mint`.
+fn close_empty_account<'info>(
+    user: &Signer<'info>,
+    token_account: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+    vault: &mut Account<'info, VaultAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    dust_threshold: u64,
+    mint: Option<&InterfaceAccount<'info, token_interface::Mint>>,
+) -> Result<u64> {
+    // Security: the signer must be the owner or the account's close authority
+    require!(
+        token_account.owner == user.key()
+            || token_account.close_authority == COption::Some(user.key()),
+        BurnerError::UnauthorizedAccount
+    );
+    require_close_authority(token_account.close_authority, &user.key())?;
+
+    // Rent always lands in the true owner's vault, even when a delegate signs
+    require!(
+        vault.owner == token_account.owner,
+        BurnerError::VaultOwnerMismatch
+    );
+    
+    // Verify the token account is empty, or holds no more than dust
+    require!(
+        token_account.amount <= dust_threshold,
+        BurnerError::AccountNotEmpty
+    );
+
+    let dust = token_account.amount;
+    if dust > 0 {
+        let mint = mint.ok_or(BurnerError::MintRequiredForDust)?;
+        let burn_accounts = token_interface::Burn {
+            mint: mint.to_account_info(),
+            from: token_account.to_account_info(),
+            authority: user.to_account_info(),
+        };
+        token_interface::burn(CpiContext::new(token_program.to_account_info(), burn_accounts), dust)?;
+        msg!("Burned {} tokens of dust", dust);
+    }
+    
+    #[cfg(not(feature = "compact-logs"))]
+    msg!(
+        "Closing token account - Mint: {}, Owner: {}",
+        token_account.mint,
+        token_account.owner
+    );
+    
+    // Re-read right before the CPI so the emptiness check can't act on a stale
+    // snapshot if anything earlier in this instruction touched the account
+    token_account.reload()?;
+    require!(
+        token_account.amount == 0,
+        BurnerError::AccountNotEmpty
+    );
+    
+    let vault_lamports_before = vault.to_account_info().lamports();
+    
+    // Create CPI context for closing the token account
+    let cpi_accounts = token_interface::CloseAccount {
+        account: token_account.to_account_info(),
+        destination: vault.to_account_info(),
+        authority: user.to_account_info(),
+    };
+    
+    let cpi_program = token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    
+    // Close the token account - rent goes to vault
+    token_interface::close_account(cpi_ctx)?;
+    
+    // Update vault lamports collected (optional tracking)
+    let rent_lamports = record_reclaimed_rent(vault, vault_lamports_before, 1, dust)?;
+    
+    logs::account_closed(&token_account.mint, &token_account.owner, rent_lamports);
+    Ok(rent_lamports)
+}
+
+// Account context for `close_token_account`
+// Designed to work efficiently with ALTs for batch operations
+#[derive(Accounts)]
+pub struct CloseTokenAccount<'info> {
+    /// Token account owner, or its close authority
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// SPL Token or Token-2022 account to close (must be empty)
+    /// Using AccountInfo instead of InterfaceAccount<TokenAccount> would be more ALT-friendly
+    /// but InterfaceAccount<TokenAccount> provides better type safety for now
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    /// Token account owner's vault PDA to receive the rent lamports
+    #[account(
+        mut,
+        seeds = [b"vault", token_account.owner.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+    
+    /// Global state PDA, read for `dust_threshold`
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+    
+    /// Mint of `token_account`; required only when there is dust to burn
+    #[account(mut, constraint = mint.key() == token_account.mint @ BurnerError::MintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Optional `ReclaimHistory` of the token account owner, gets a record of this close
+    #[account(mut, seeds = [b"history", token_account.owner.as_ref()], bump = history.bump)]
+    pub history: Option<Box<Account<'info, ReclaimHistory>>>,
+    
+    /// CHECK: instructions sysvar, required while `state.check_duplicate_closes` is on
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Account context for `close_token_account_lean`
+// Minimal hot path: no state account, and the vault is only a lamport destination
+#[derive(Accounts)]
+#[instruction(vault_bump: u8)]
+pub struct CloseTokenAccountLean<'info> {
+    /// Owner of the token account
+    pub user: Signer<'info>,
+
+    /// CHECK: SPL Token or Token-2022 account to close (must be empty); taken as a
+    /// bare account to skip the typed load, and parsed by `load_token_account` instead
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// CHECK: the user's existing vault PDA, pinned by seeds and program ownership;
+    /// receives lamports only, its data is never read
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault_bump,
+        owner = crate::ID @ BurnerError::InvalidVaultAccount
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Account context for `close_with_vault_init`
+// Same as `CloseTokenAccount`, but creates the vault first if it doesn't exist yet
+#[derive(Accounts)]
+pub struct CloseWithVaultInit<'info> {
+    /// User who owns the token account; pays the vault's rent on first use
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// SPL Token or Token-2022 account to close (must be empty)
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    /// User's vault PDA, created on the fly; owner is checked in the handler
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + VaultAccount::INIT_SPACE,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, read for `vault_creation_paused`
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+    
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program (required by `init_if_needed` to create the vault)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `unwrap_and_close_wsol`
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UnwrapAndCloseWsol<'info> {
+    /// Owner of the wSOL account; receives all of its lamports
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token account of the native mint, funded or empty
+    #[account(
+        mut,
+        constraint = token_account.mint == native_mint::ID @ BurnerError::NotWrappedSol
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// SPL Token program, which owns the native mint
+    pub token_program: Program<'info, Token>,
+}
+
+// Account context for `close_token_account_to`
+#[derive(Accounts)]
+pub struct CloseTokenAccountTo<'info> {
+    /// Owner of the token account
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token or Token-2022 account to close (must be empty)
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// CHECK: rent destination chosen per call; the handler only accepts the
+    /// owner's vault PDA or the owner's wallet
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,23 @@
+pub mod admin;
+pub mod batch;
+pub mod burn;
+pub mod close;
+pub mod refer;
+pub mod relay;
+pub mod recovery;
+pub mod schedule;
+pub mod shard;
+pub mod validate;
+pub mod vault;
+
+pub use admin::*;
+pub use batch::*;
+pub use burn::*;
+pub use close::*;
+pub use refer::*;
+pub use relay::*;
+pub use recovery::*;
+pub use schedule::*;
+pub use shard::*;
+pub use validate::*;
+pub use vault::*;
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ROLE_OPS, VAULT_RECOVERY_TIMELOCK_SECS};
+use crate::errors::BurnerError;
+use crate::events::{VaultRecoveryFinalized, VaultRecoveryProposed, VaultRecoveryVetoed};
+use crate::state::{BurnerState, VaultAccount};
+
+pub(crate) fn propose_vault_recovery(ctx: Context<ProposeVaultRecovery>, new_owner: Pubkey) -> Result<()> {
+    let now = ctx.accounts.state.now()?;
+    let vault = &mut ctx.accounts.vault;
+    require_keys_neq!(new_owner, vault.owner, BurnerError::InvalidOwner);
+
+    // A new proposal replaces a pending one and restarts the full veto window
+    let unlock_at = now
+        .checked_add(VAULT_RECOVERY_TIMELOCK_SECS)
+        .ok_or(BurnerError::MathOverflow)?;
+    vault.recovery_owner     = Some(new_owner);
+    vault.recovery_unlock_at = unlock_at;
+
+    emit!(VaultRecoveryProposed {
+        vault: vault.key(),
+        owner: vault.owner,
+        new_owner,
+        unlock_at,
+        authority: ctx.accounts.authority.key(),
+    });
+    msg!("Recovery of vault {} to {} proposed, final from {}", vault.owner, new_owner, unlock_at);
+    Ok(())
+}
+
+pub(crate) fn veto_recovery(ctx: Context<VetoRecovery>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let new_owner = vault.recovery_owner.ok_or(BurnerError::NoRecoveryPending)?;
+
+    // No time check: until someone finalizes, the owner can still say no
+    vault.recovery_owner     = None;
+    vault.recovery_unlock_at = 0;
+
+    emit!(VaultRecoveryVetoed {
+        vault: vault.key(),
+        owner: vault.owner,
+        new_owner,
+    });
+    msg!("Vault {} vetoed recovery to {}", vault.owner, new_owner);
+    Ok(())
+}
+
+pub(crate) fn finalize_vault_recovery(ctx: Context<FinalizeVaultRecovery>) -> Result<()> {
+    let now = ctx.accounts.state.now()?;
+    require!(now >= ctx.accounts.vault.recovery_unlock_at, BurnerError::RecoveryTimelockActive);
+
+    // The vault PDA is derived from its owner, so it can't simply change hands:
+    // its lamports and tally move into the new owner's own vault, `close` does the rest
+    let lamports = ctx.accounts.vault.to_account_info().lamports();
+    let collected = ctx.accounts.vault.lamports_collected;
+    let (tokens_burned, accounts_closed) = (ctx.accounts.vault.tokens_burned, ctx.accounts.vault.accounts_closed);
+    let new_vault = &mut ctx.accounts.new_vault;
+    new_vault.lamports_collected = new_vault
+        .lamports_collected
+        .checked_add(collected)
+        .ok_or(BurnerError::MathOverflow)?;
+    new_vault.tokens_burned = new_vault
+        .tokens_burned
+        .checked_add(tokens_burned)
+        .ok_or(BurnerError::MathOverflow)?;
+    new_vault.accounts_closed = new_vault
+        .accounts_closed
+        .checked_add(accounts_closed)
+        .ok_or(BurnerError::MathOverflow)?;
+
+    emit!(VaultRecoveryFinalized {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        new_owner: ctx.accounts.new_owner.key(),
+        new_vault: new_vault.key(),
+        lamports,
+    });
+    msg!(
+        "Vault {} recovered to {}: {} lamports moved",
+        ctx.accounts.vault.owner,
+        new_vault.owner,
+        lamports
+    );
+    Ok(())
+}
+
+// Account context for `propose_vault_recovery`
+#[derive(Accounts)]
+pub struct ProposeVaultRecovery<'info> {
+    /// Program authority, or the `ROLE_OPS` holder
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.is_authorized(ROLE_OPS, &authority.key()) @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    /// Vault being recovered; re-derived from its stored owner
+    #[account(
+        mut,
+        seeds = [b"vault", vault.owner.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+}
+
+// Account context for `veto_recovery`
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `finalize_vault_recovery`
+#[derive(Accounts)]
+pub struct FinalizeVaultRecovery<'info> {
+    /// Owner named by the pending recovery
+    pub new_owner: Signer<'info>,
+
+    /// Vault being recovered; closed into `new_vault`
+    #[account(
+        mut,
+        seeds = [b"vault", vault.owner.as_ref()],
+        bump = vault.bump,
+        constraint = vault.recovery_owner == Some(new_owner.key()) @ BurnerError::NoRecoveryPending,
+        close = new_vault
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", new_owner.key().as_ref()],
+        bump = new_vault.bump,
+        constraint = new_vault.owner == new_owner.key() @ BurnerError::InvalidOwner
+    )]
+    pub new_vault: Account<'info, VaultAccount>, // new owner's own vault, created beforehand
+
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read for the clock
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::constants::{EVENT_LEVEL_ROLLUP, MINT_BURN_LIMIT_SEED};
+use crate::errors::BurnerError;
+use crate::events::RentSplit;
+use crate::instructions::close::{reclaimed_lamports, require_close_authority};
+use crate::state::{BurnerState, MintBurnLimit, MintFeeOverride, VaultAccount};
+
+pub(crate) fn burn_close_refer(ctx: Context<BurnCloseRefer>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let token_account = &ctx.accounts.token_account;
+    let user = &ctx.accounts.user;
+
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+    require_close_authority(token_account.close_authority, &user.key())?;
+
+    let token_amount = token_account.amount;
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if token_amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token_interface::burn(burn_ctx, token_amount)?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+
+    // All rent lands in the user's vault first, then gets split out of it
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    token_interface::close_account(close_ctx)?;
+
+    let rent_lamports = reclaimed_lamports(&ctx.accounts.vault.to_account_info(), vault_lamports_before)?;
+    let mint_fee = MintFeeOverride::load(&ctx.accounts.mint_fee)?;
+    let split = ctx.accounts.state.split_rent(rent_lamports, mint_fee.as_ref())?;
+
+    // The vault is program-owned, so it can be debited directly
+    let vault_ai = ctx.accounts.vault.to_account_info();
+    let rent_floor = ctx.accounts.vault.rent_floor(vault_ai.data_len())?;
+    let (user_share, referral_share, treasury_share) = keep_vault_rent_exempt(vault_ai.lamports(), rent_floor, split);
+    **vault_ai.try_borrow_mut_lamports()? -= referral_share + treasury_share;
+    **ctx.accounts.referrer_vault.to_account_info().try_borrow_mut_lamports()? += referral_share;
+    **ctx.accounts.treasury.try_borrow_mut_lamports()? += treasury_share;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.lamports_collected = vault.lamports_collected.saturating_add(user_share);
+    vault.record_closes(1, token_amount);
+    let referrer_vault = &mut ctx.accounts.referrer_vault;
+    referrer_vault.lamports_collected = referrer_vault.lamports_collected.saturating_add(referral_share);
+
+    if ctx.accounts.state.emits(EVENT_LEVEL_ROLLUP) {
+        emit!(RentSplit {
+            user:           user.key(),
+            referrer:       referrer_vault.owner,
+            token_account:  ctx.accounts.token_account.key(),
+            user_share,
+            referral_share,
+            treasury_share,
+        });
+    }
+
+    msg!(
+        "Burned {} tokens, rent split {} user / {} referrer / {} treasury",
+        token_amount,
+        user_share,
+        referral_share,
+        treasury_share
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+/// Shifts lamports from the treasury share, then the referral share, back to the
+/// user's until paying both out leaves `vault_lamports` at or above `rent_floor`.
+/// Paying out at most the reclaimed rent can only dip under the floor if the vault
+/// was already below it, e.g. after a rent increase; otherwise the split is unchanged.
+fn keep_vault_rent_exempt(vault_lamports: u64, rent_floor: u64, split: (u64, u64, u64)) -> (u64, u64, u64) {
+    let (mut user, mut referral, mut treasury) = split;
+    let mut shortfall = rent_floor.saturating_sub(vault_lamports.saturating_sub(referral + treasury));
+    for share in [&mut treasury, &mut referral] {
+        let diverted = shortfall.min(*share);
+        *share -= diverted;
+        user += diverted;
+        shortfall -= diverted;
+    }
+    (user, referral, treasury)
+}
+
+// Account context for `burn_close_refer`
+#[derive(Accounts)]
+pub struct BurnCloseRefer<'info> {
+    /// User who owns the token account
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token or Token-2022 account to burn and close
+    #[account(mut)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The token account's mint; keys the fee override, so it can't be swapped
+    /// for another even when the burn CPI is skipped
+    #[account(mut, constraint = mint.key() == token_account.mint @ BurnerError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// User's vault PDA, keeps the remainder of the rent
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Referrer's vault PDA, receives `referral_bps` of the rent
+    #[account(
+        mut,
+        seeds = [b"vault", referrer_vault.owner.as_ref()],
+        bump = referrer_vault.bump,
+        constraint = referrer_vault.owner != user.key() @ BurnerError::SelfReferral
+    )]
+    pub referrer_vault: Account<'info, VaultAccount>,
+
+    /// CHECK: lamport destination only, pinned to the configured treasury
+    #[account(mut, address = state.treasury @ BurnerError::InvalidOwner)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Global config PDA: rent split config, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// CHECK: the mint's `MintFeeOverride` address; once initialized its `fee_bps`
+    /// replaces `state.treasury_bps` (see `MintFeeOverride::load`)
+    #[account(seeds = [b"mint_fee", mint.key().as_ref()], bump)]
+    pub mint_fee: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program owning the token account
+    pub token_program: Interface<'info, TokenInterface>,
+}
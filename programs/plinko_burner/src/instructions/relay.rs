@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, CloseAccount, close_account, Burn, burn};
+
+use crate::constants::{IDEMPOTENCY_RETENTION_SECS, MINT_BURN_LIMIT_SEED};
+use crate::errors::BurnerError;
+use crate::events::{OperatorFeePaid, VaultRentReimbursed};
+use crate::instructions::close::{reclaimed_lamports, require_close_authority};
+use crate::state::{BurnerState, IdempotencyRecord, MintBurnLimit, VaultAccount};
+
+pub(crate) fn relayed_burn_and_close(
+    ctx: Context<RelayedBurnAndClose>,
+    idempotency_key: [u8; 32],
+    operator_fee_lamports: Option<u64>,
+) -> Result<()> {
+    // A non-zero timestamp means this key already went through
+    if ctx.accounts.idempotency_record.processed_at != 0 {
+        msg!(
+            "Idempotency key already processed at {}, skipping",
+            ctx.accounts.idempotency_record.processed_at
+        );
+        return Ok(());
+    }
+
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    // First relayed burn for this user: init_if_needed just allocated the vault
+    // on the relayer's dime, so fill it in and remember what that cost
+    let mut vault_creation_rent = 0;
+    if ctx.accounts.vault.owner == Pubkey::default() {
+        require!(!ctx.accounts.state.vault_creation_paused, BurnerError::VaultCreationPaused);
+        vault_creation_rent = Rent::get()?.minimum_balance(ctx.accounts.vault.to_account_info().data_len());
+        let vault = &mut ctx.accounts.vault;
+        vault.set_inner(VaultAccount::init(ctx.accounts.user.key(), ctx.bumps.vault, vault_creation_rent));
+        msg!("Vault created for user: {}", vault.owner);
+    }
+    require!(ctx.accounts.vault.owner == ctx.accounts.user.key(), BurnerError::InvalidOwner);
+
+    // First submission: the token account must still be a live SPL account
+    let token_account_info = ctx.accounts.token_account.to_account_info();
+    require_keys_eq!(
+        *token_account_info.owner,
+        Token::id(),
+        BurnerError::NotATokenAccount
+    );
+    let token_account = TokenAccount::try_deserialize(&mut &token_account_info.data.borrow()[..])
+        .map_err(|_| BurnerError::NotATokenAccount)?;
+
+    // Security: Verify the token account owner matches the user
+    require!(
+        token_account.owner == ctx.accounts.user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+    require_close_authority(token_account.close_authority, &ctx.accounts.user.key())?;
+
+    let token_amount = token_account.amount;
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.state.record_burn(token_amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, token_amount, now)?;
+
+    if token_amount > 0 {
+        let burn_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: token_account_info.clone(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        burn(burn_ctx, token_amount)?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+    let close_accounts = CloseAccount {
+        account: token_account_info,
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
+    close_account(close_ctx)?;
+
+    // Update vault lamports collected (optional tracking)
+    let vault = &mut ctx.accounts.vault;
+    let rent_lamports = reclaimed_lamports(&vault.to_account_info(), vault_lamports_before)?;
+    let operator_fee = operator_fee_lamports.unwrap_or(0);
+
+    if operator_fee > 0 {
+        // Security: only an operator the user approved, and never more than was reclaimed
+        require!(
+            vault.operator == Some(ctx.accounts.payer.key()),
+            BurnerError::UnauthorizedOperator
+        );
+        require!(operator_fee <= rent_lamports, BurnerError::OperatorFeeTooHigh);
+
+        **vault.to_account_info().try_borrow_mut_lamports()? -= operator_fee;
+        **ctx.accounts.payer.to_account_info().try_borrow_mut_lamports()? += operator_fee;
+
+        // Not gated on `event_level`: it's the operator's only on-chain receipt of a
+        // payout out of somebody else's vault, not burn telemetry
+        emit!(OperatorFeePaid {
+            user:     ctx.accounts.user.key(),
+            operator: ctx.accounts.payer.key(),
+            fee:      operator_fee,
+            rent:     rent_lamports,
+        });
+    }
+
+    // Pay the relayer back for creating the vault, out of what's left of the
+    // reclaimed rent – never more than the creation cost or than was reclaimed
+    let reimbursement = vault_creation_rent.min(rent_lamports - operator_fee);
+    if reimbursement > 0 {
+        **vault.to_account_info().try_borrow_mut_lamports()? -= reimbursement;
+        **ctx.accounts.payer.to_account_info().try_borrow_mut_lamports()? += reimbursement;
+
+        // Not gated either, for the same reason: relayers reconcile what they got back from it
+        emit!(VaultRentReimbursed {
+            user:          ctx.accounts.user.key(),
+            payer:         ctx.accounts.payer.key(),
+            reimbursement,
+            creation_rent: vault_creation_rent,
+        });
+    }
+    let user_share = rent_lamports - operator_fee - reimbursement;
+    vault.lamports_collected = vault.lamports_collected.saturating_add(user_share);
+    vault.record_closes(1, token_amount);
+
+    // Mark the key as processed – only persists if the whole tx succeeds
+    let record = &mut ctx.accounts.idempotency_record;
+    record.user            = ctx.accounts.user.key();
+    record.payer           = ctx.accounts.payer.key();
+    record.idempotency_key = idempotency_key;
+    record.processed_at    = now;
+    record.bump            = ctx.bumps.idempotency_record;
+
+    msg!(
+        "Relayed burn of {} tokens from mint {}, {} lamports sent to vault, {} to operator, {} to relayer",
+        token_amount,
+        token_account.mint,
+        user_share,
+        operator_fee,
+        reimbursement
+    );
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn close_idempotency_record(ctx: Context<CloseIdempotencyRecord>) -> Result<()> {
+    let record = &ctx.accounts.idempotency_record;
+    let now = ctx.accounts.state.now()?;
+
+    require!(
+        now >= record.processed_at.saturating_add(IDEMPOTENCY_RETENTION_SECS),
+        BurnerError::IdempotencyRecordTooRecent
+    );
+
+    msg!("Idempotency record closed, rent refunded to {}", record.payer);
+    Ok(())
+}
+
+// Account context for `relayed_burn_and_close`
+// Token account and mint are unchecked so that a retry still deserializes
+// after the first submission already closed the token account.
+#[derive(Accounts)]
+#[instruction(idempotency_key: [u8; 32])]
+pub struct RelayedBurnAndClose<'info> {
+    /// Relayer submitting the tx; pays the record rent, and the vault rent on
+    /// first use (reimbursed from the reclaimed rent)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// User who owns the token account (signs as token authority)
+    pub user: Signer<'info>,
+
+    /// CHECK: validated in the handler on first submission; may already be closed on retry
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// CHECK: passed straight to the burn CPI, which checks it against the token account
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// User's vault PDA to receive the rent lamports, created on the fly;
+    /// owner is checked in the handler
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultAccount::INIT_SPACE,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global config PDA, books the burn against the daily limit
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// Marker PDA for this key, created on first successful processing.
+    /// Always re-derived: accepting a client bump here would let a
+    /// non-canonical bump create a second record for the same key.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IdempotencyRecord::INIT_SPACE,
+        seeds = [b"idempotency", user.key().as_ref(), idempotency_key.as_ref()],
+        bump
+    )]
+    pub idempotency_record: Account<'info, IdempotencyRecord>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `close_idempotency_record`
+#[derive(Accounts)]
+pub struct CloseIdempotencyRecord<'info> {
+    /// Relayer that originally funded the record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Global state PDA, read for the current time
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            b"idempotency",
+            idempotency_record.user.as_ref(),
+            idempotency_record.idempotency_key.as_ref()
+        ],
+        bump = idempotency_record.bump,
+        constraint = idempotency_record.payer == payer.key() @ BurnerError::InvalidOwner
+    )]
+    pub idempotency_record: Account<'info, IdempotencyRecord>,
+}
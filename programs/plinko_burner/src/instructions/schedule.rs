@@ -0,0 +1,312 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{EVENT_LEVEL_FULL, MINT_BURN_LIMIT_SEED, SCHEDULED_BURN_SEED, SCHEDULED_ESCROW_SEED};
+use crate::errors::BurnerError;
+use crate::events::{BurnScheduled, ScheduledBurnCancelled, ScheduledBurnExecuted};
+use crate::instructions::close::record_reclaimed_rent;
+use crate::logs;
+use crate::state::{BurnerState, MintBurnLimit, ScheduledBurn, VaultAccount};
+
+pub(crate) fn schedule_burn(ctx: Context<ScheduleBurn>, schedule_id: u64, amount: u64, unlock_at: i64) -> Result<()> {
+    require!(amount > 0, BurnerError::ZeroScheduledAmount);
+    let now = ctx.accounts.state.now()?;
+    require!(unlock_at > now, BurnerError::InvalidUnlockTime);
+
+    let transfer_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.source.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.escrow.to_account_info(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.depositor   = ctx.accounts.depositor.key();
+    schedule.mint        = ctx.accounts.mint.key();
+    schedule.escrow      = ctx.accounts.escrow.key();
+    schedule.schedule_id = schedule_id;
+    schedule.amount      = amount;
+    schedule.created_at  = now;
+    schedule.unlock_at   = unlock_at;
+    schedule.bump        = ctx.bumps.schedule;
+
+    emit!(BurnScheduled {
+        depositor: schedule.depositor,
+        mint:      schedule.mint,
+        schedule:  schedule.key(),
+        amount,
+        unlock_at,
+    });
+    msg!("{} tokens of {} scheduled to burn at {}", amount, schedule.mint, unlock_at);
+    Ok(())
+}
+
+pub(crate) fn execute_scheduled_burn(ctx: Context<ExecuteScheduledBurn>) -> Result<()> {
+    BurnerState::begin_processing(&mut ctx.accounts.state, ctx.program_id)?;
+
+    let now = ctx.accounts.state.now()?;
+    require!(now >= ctx.accounts.schedule.unlock_at, BurnerError::BurnNotUnlocked);
+
+    // Burn whatever the escrow holds, or the close below would fail on a top-up
+    let amount = ctx.accounts.escrow.amount;
+    ctx.accounts.state.record_burn(amount, now)?;
+    MintBurnLimit::record_if_set(&ctx.accounts.mint_burn_limit, amount, now)?;
+
+    let schedule = &ctx.accounts.schedule;
+    let schedule_id = schedule.schedule_id.to_le_bytes();
+    let schedule_seeds: &[&[u8]] = &[
+        SCHEDULED_BURN_SEED,
+        schedule.depositor.as_ref(),
+        schedule.mint.as_ref(),
+        &schedule_id,
+        &[schedule.bump],
+    ];
+    let signer_seeds = &[schedule_seeds];
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    if amount > 0 {
+        let burn_accounts = token_interface::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.escrow.to_account_info(),
+            authority: schedule.to_account_info(),
+        };
+        token_interface::burn(
+            CpiContext::new_with_signer(token_program.clone(), burn_accounts, signer_seeds),
+            amount,
+        )?;
+    }
+
+    let vault_lamports_before = ctx.accounts.vault.to_account_info().lamports();
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.escrow.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: schedule.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(token_program, close_accounts, signer_seeds))?;
+    // The schedule PDA's own rent follows via `close = vault` and isn't counted
+    let rent_lamports = record_reclaimed_rent(&mut ctx.accounts.vault, vault_lamports_before, 1, amount)?;
+
+    let schedule = &ctx.accounts.schedule;
+    // A per-account burn event, so it follows the same level as burn_and_close's
+    if ctx.accounts.state.emits(EVENT_LEVEL_FULL) {
+        emit!(ScheduledBurnExecuted {
+            depositor: schedule.depositor,
+            mint:      schedule.mint,
+            schedule:  schedule.key(),
+            amount,
+            executor:  ctx.accounts.executor.key(),
+        });
+    }
+    logs::burned_and_closed(&schedule.mint, &schedule.depositor, amount, rent_lamports);
+    ctx.accounts.state.end_processing();
+    Ok(())
+}
+
+pub(crate) fn cancel_scheduled_burn(ctx: Context<CancelScheduledBurn>) -> Result<()> {
+    let now = ctx.accounts.state.now()?;
+    // From unlock_at on the burn is committed, even while nobody has executed it yet
+    require!(now < ctx.accounts.schedule.unlock_at, BurnerError::BurnCommitted);
+
+    let schedule = &ctx.accounts.schedule;
+    let schedule_id = schedule.schedule_id.to_le_bytes();
+    let schedule_seeds: &[&[u8]] = &[
+        SCHEDULED_BURN_SEED,
+        schedule.depositor.as_ref(),
+        schedule.mint.as_ref(),
+        &schedule_id,
+        &[schedule.bump],
+    ];
+    let signer_seeds = &[schedule_seeds];
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    let amount = ctx.accounts.escrow.amount;
+    if amount > 0 {
+        let transfer_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.escrow.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: schedule.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(token_program.clone(), transfer_accounts, signer_seeds),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let close_accounts = token_interface::CloseAccount {
+        account: ctx.accounts.escrow.to_account_info(),
+        destination: ctx.accounts.vault.to_account_info(),
+        authority: schedule.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(token_program, close_accounts, signer_seeds))?;
+
+    emit!(ScheduledBurnCancelled {
+        depositor: schedule.depositor,
+        mint:      schedule.mint,
+        schedule:  schedule.key(),
+        amount,
+    });
+    msg!("Scheduled burn of {} tokens of {} cancelled", amount, schedule.mint);
+    Ok(())
+}
+
+// Account context for `schedule_burn`
+#[derive(Accounts)]
+#[instruction(schedule_id: u64)]
+pub struct ScheduleBurn<'info> {
+    /// Owner of the tokens; pays the schedule's and the escrow's rent
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// Token account the tokens are escrowed from
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = source.owner == depositor.key() @ BurnerError::UnauthorizedAccount
+    )]
+    pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Depositor's vault, where the rent goes once the schedule is closed; must exist
+    /// now so an execution can't strand on a missing vault
+    #[account(
+        seeds = [b"vault", depositor.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == depositor.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global state PDA, read for the clock
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + ScheduledBurn::INIT_SPACE,
+        seeds = [SCHEDULED_BURN_SEED, depositor.key().as_ref(), mint.key().as_ref(), &schedule_id.to_le_bytes()],
+        bump
+    )]
+    pub schedule: Account<'info, ScheduledBurn>,
+
+    /// Program-owned escrow: its authority is the schedule PDA, so only this
+    /// program can move the tokens out again
+    #[account(
+        init,
+        payer = depositor,
+        seeds = [SCHEDULED_ESCROW_SEED, schedule.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = schedule,
+        token::token_program = token_program
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL Token or Token-2022 program of the mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `execute_scheduled_burn` – permissionless once unlocked
+#[derive(Accounts)]
+pub struct ExecuteScheduledBurn<'info> {
+    /// Anyone; pays only the transaction fee
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SCHEDULED_BURN_SEED,
+            schedule.depositor.as_ref(),
+            schedule.mint.as_ref(),
+            &schedule.schedule_id.to_le_bytes()
+        ],
+        bump = schedule.bump,
+        close = vault
+    )]
+    pub schedule: Account<'info, ScheduledBurn>,
+
+    #[account(mut, address = schedule.escrow)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = schedule.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Depositor's vault, receives the escrow's and the schedule's rent
+    #[account(
+        mut,
+        seeds = [b"vault", schedule.depositor.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global state PDA; the burn counts against the daily limit and pause
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// CHECK: the mint's `MintBurnLimit` address, enforced once initialized (see `MintBurnLimit::record_if_set`)
+    #[account(mut, seeds = [MINT_BURN_LIMIT_SEED, mint.key().as_ref()], bump)]
+    pub mint_burn_limit: UncheckedAccount<'info>,
+
+    /// SPL Token or Token-2022 program of the mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Account context for `cancel_scheduled_burn`
+#[derive(Accounts)]
+pub struct CancelScheduledBurn<'info> {
+    /// Depositor of the schedule; nobody else can take the tokens back
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SCHEDULED_BURN_SEED,
+            schedule.depositor.as_ref(),
+            schedule.mint.as_ref(),
+            &schedule.schedule_id.to_le_bytes()
+        ],
+        bump = schedule.bump,
+        constraint = schedule.depositor == depositor.key() @ BurnerError::UnauthorizedAccount,
+        close = vault
+    )]
+    pub schedule: Account<'info, ScheduledBurn>,
+
+    #[account(mut, address = schedule.escrow)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = schedule.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Depositor's token account the escrowed tokens go back to
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = destination.owner == depositor.key() @ BurnerError::UnauthorizedAccount
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Depositor's vault, receives the escrow's and the schedule's rent
+    #[account(
+        mut,
+        seeds = [b"vault", depositor.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultAccount>,
+
+    /// Global state PDA, read for the clock
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    /// SPL Token or Token-2022 program of the mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
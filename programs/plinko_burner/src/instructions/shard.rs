@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_BATCH_ACCOUNTS, NUM_SHARDS};
+use crate::errors::BurnerError;
+use crate::state::{BurnerState, ShardCounter};
+
+pub(crate) fn init_shard(ctx: Context<InitShard>, index: u8) -> Result<()> {
+    require!(index < NUM_SHARDS, BurnerError::InvalidShardIndex);
+
+    let shard = &mut ctx.accounts.shard;
+    shard.index          = index;
+    shard.pending_burned = 0;
+    shard.pending_closed = 0;
+    shard.pending_rent   = 0;
+    shard.bump           = ctx.bumps.shard;
+
+    msg!("Shard {} created", index);
+    Ok(())
+}
+
+pub(crate) fn fold_shards<'info>(ctx: Context<'_, '_, 'info, 'info, FoldShards<'info>>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let mut folded_burned: u128 = 0;
+    let mut folded_closed: u64 = 0;
+    let mut folded_rent: u128 = 0;
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+
+    for shard_info in ctx.remaining_accounts {
+        let mut shard = Account::<ShardCounter>::try_from(shard_info)
+            .map_err(|_| BurnerError::InvalidShardAccount)?;
+
+        // Security: only the canonical PDA for its index may be drained
+        let expected = Pubkey::create_program_address(
+            &[b"shard".as_ref(), &[shard.index], &[shard.bump]],
+            ctx.program_id,
+        )
+        .map_err(|_| BurnerError::InvalidShardAccount)?;
+        require_keys_eq!(expected, shard_info.key(), BurnerError::InvalidShardAccount);
+
+        folded_burned = folded_burned
+            .checked_add(shard.pending_burned)
+            .ok_or(BurnerError::MathOverflow)?;
+        folded_closed = folded_closed
+            .checked_add(shard.pending_closed)
+            .ok_or(BurnerError::MathOverflow)?;
+        folded_rent = folded_rent
+            .checked_add(shard.pending_rent as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+
+        shard.pending_burned = 0;
+        shard.pending_closed = 0;
+        shard.pending_rent   = 0;
+        shard.exit(ctx.program_id)?; // persist the drained shard
+    }
+
+    state.total_burned = state
+        .total_burned
+        .checked_add(folded_burned)
+        .ok_or(BurnerError::MathOverflow)?;
+    state.accounts_closed = state
+        .accounts_closed
+        .checked_add(folded_closed)
+        .ok_or(BurnerError::MathOverflow)?;
+    state.total_rent_reclaimed = state
+        .total_rent_reclaimed
+        .checked_add(folded_rent)
+        .ok_or(BurnerError::MathOverflow)?;
+    state.last_folded_at = state.now()?;
+
+    msg!(
+        "Folded {} shards: +{} burned, +{} closed",
+        ctx.remaining_accounts.len(),
+        folded_burned,
+        folded_closed
+    );
+    Ok(())
+}
+
+// Account context for `init_shard`
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct InitShard<'info> {
+    /// Program authority, pays for the shard
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ShardCounter::INIT_SPACE,
+        seeds = [b"shard".as_ref(), &[index]],
+        bump
+    )]
+    pub shard: Account<'info, ShardCounter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `fold_shards`
+// Shards to drain are passed as writable remaining_accounts.
+#[derive(Accounts)]
+pub struct FoldShards<'info> {
+    /// Program authority stored on the state PDA
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.state_bump,
+        constraint = state.authority == authority.key() @ BurnerError::UnauthorizedAuthority
+    )]
+    pub state: Account<'info, BurnerState>,
+}
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    immutable_owner::ImmutableOwner, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface;
+
+use crate::constants::{CLASS_BURNABLE, CLASS_EMPTY, CLASS_FROZEN, CLASS_WRONG_OWNER, MAX_BATCH_ACCOUNTS, MAX_BPS,
+    MAX_DETAILED_ACCOUNTS};
+use crate::errors::BurnerError;
+use crate::events::ValidationReport;
+
+// Return data of `simulate_burn_impact`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BurnImpact {
+    pub supply: u64,           // mint supply right now
+    pub resulting_supply: u64, // supply after the hypothetical burn
+    pub reduction_bps: u16,    // amount as bps of supply, rounded down; 0 for a zero-supply mint
+}
+
+// One `classify_batch_with_rent` entry, 9 bytes serialized
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClassifiedAccount {
+    pub category: u8, // CLASS_* byte, as classify_batch returns
+    pub rent: u64,    // lamports the account holds right now, reclaimed if it is closed
+}
+
+// One `validate_batch_detailed` entry; MAX_DETAILED_ACCOUNTS depends on its 73-byte size
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DetailedAccount {
+    pub pubkey: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub owner_matches: bool, // token account owner is the `user` the batch was read for
+}
+
+pub(crate) fn validate_token_account(ctx: Context<ValidateTokenAccount>) -> Result<()> {
+    let token_account = &ctx.accounts.token_account;
+    let user = &ctx.accounts.user;
+    
+    // Security: Verify the token account owner matches the signer
+    require!(
+        token_account.owner == user.key(),
+        BurnerError::UnauthorizedAccount
+    );
+    
+    // Log account details for debugging
+    msg!(
+        "Valid token account - Mint: {}, Balance: {}, Owner: {}",
+        token_account.mint,
+        token_account.amount,
+        token_account.owner
+    );
+    
+    // Check if account is empty (will be useful in later stages)
+    if token_account.amount == 0 {
+        msg!("Token account is empty and ready to close");
+    } else {
+        msg!("Token account has {} tokens", token_account.amount);
+    }
+
+    // Decimals are only known when the client passed the mint
+    let decimals = ctx.accounts.mint.as_ref().map(|mint| mint.decimals);
+    match decimals {
+        Some(decimals) => msg!("Mint decimals: {}", decimals),
+        None => msg!("Mint not provided, skipping decimals"),
+    }
+
+    let immutable_owner = has_immutable_owner(&token_account.to_account_info())?;
+    msg!("Immutable owner: {}", immutable_owner);
+
+    emit!(ValidationReport {
+        mint:     token_account.mint,
+        amount:   token_account.amount,
+        decimals,
+        is_empty: token_account.amount == 0,
+        immutable_owner,
+    });
+    
+    Ok(())
+}
+
+pub(crate) fn classify_batch(ctx: Context<ClassifyBatch>) -> Result<Vec<u8>> {
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+
+    let user = ctx.accounts.user.key();
+    let categories: Vec<u8> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|info| classify_account(info, &user))
+        .collect();
+
+    msg!("Classified {} accounts", categories.len());
+    Ok(categories)
+}
+
+pub(crate) fn classify_batch_with_rent(ctx: Context<ClassifyBatch>) -> Result<Vec<ClassifiedAccount>> {
+    // 4 + 9 * MAX_BATCH_ACCOUNTS bytes, comfortably inside the 1024-byte return data limit
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+
+    let user = ctx.accounts.user.key();
+    let classified: Vec<ClassifiedAccount> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|info| ClassifiedAccount {
+            category: classify_account(info, &user),
+            rent: info.lamports(),
+        })
+        .collect();
+
+    msg!("Classified {} accounts with rent", classified.len());
+    Ok(classified)
+}
+
+pub(crate) fn validate_batch_detailed(ctx: Context<ClassifyBatch>) -> Result<Vec<DetailedAccount>> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_DETAILED_ACCOUNTS,
+        BurnerError::BatchTooLarge
+    );
+
+    let user = ctx.accounts.user.key();
+    let mut details = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        // Unlike classify_batch, a confirmation screen can't show a non-token account
+        require!(
+            *info.owner == Token::id() || *info.owner == Token2022::id(),
+            BurnerError::NotATokenAccount
+        );
+        let token_account = token_interface::TokenAccount::try_deserialize(&mut &info.data.borrow()[..])
+            .map_err(|_| BurnerError::NotATokenAccount)?;
+
+        details.push(DetailedAccount {
+            pubkey: info.key(),
+            mint: token_account.mint,
+            amount: token_account.amount,
+            owner_matches: token_account.owner == user,
+        });
+    }
+
+    msg!("Validated {} accounts in detail", details.len());
+    Ok(details)
+}
+
+pub(crate) fn simulate_burn_impact(ctx: Context<SimulateBurnImpact>, amount: u64) -> Result<BurnImpact> {
+    let supply = ctx.accounts.mint.supply;
+    let resulting_supply = supply
+        .checked_sub(amount)
+        .ok_or(BurnerError::BurnExceedsSupply)?;
+
+    // amount <= supply here, so the share is at most MAX_BPS and a zero supply
+    // can only mean a zero amount
+    let reduction_bps = if supply == 0 {
+        0
+    } else {
+        (amount as u128)
+            .checked_mul(MAX_BPS as u128)
+            .ok_or(BurnerError::MathOverflow)?
+            .checked_div(supply as u128)
+            .ok_or(BurnerError::MathOverflow)? as u16
+    };
+
+    msg!(
+        "Burning {} of {} reduces supply to {} ({} bps)",
+        amount,
+        supply,
+        resulting_supply,
+        reduction_bps
+    );
+    Ok(BurnImpact {
+        supply,
+        resulting_supply,
+        reduction_bps,
+    })
+}
+
+/// Whether a token account carries Token-2022's `ImmutableOwner` extension, i.e. its
+/// owner can never be reassigned. Legacy SPL Token accounts have no extensions.
+fn has_immutable_owner(info: &AccountInfo) -> Result<bool> {
+    if *info.owner != Token2022::id() {
+        return Ok(false);
+    }
+    let data = info.try_borrow_data()?;
+    let account = StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(account.get_extension::<ImmutableOwner>().is_ok())
+}
+
+/// `CLASS_*` byte for one account. Anything that isn't an SPL Token or
+/// Token-2022 account owned by `user` counts as `CLASS_WRONG_OWNER`.
+pub(crate) fn classify_account(info: &AccountInfo, user: &Pubkey) -> u8 {
+    if *info.owner != Token::id() && *info.owner != Token2022::id() {
+        return CLASS_WRONG_OWNER;
+    }
+    let Ok(token_account) = token_interface::TokenAccount::try_deserialize(&mut &info.data.borrow()[..]) else {
+        return CLASS_WRONG_OWNER;
+    };
+
+    if token_account.owner != *user {
+        CLASS_WRONG_OWNER
+    } else if token_account.is_frozen() {
+        CLASS_FROZEN
+    } else if token_account.amount > 0 {
+        CLASS_BURNABLE
+    } else {
+        CLASS_EMPTY
+    }
+}
+
+// Account context for `validate_token_account`
+#[derive(Accounts)]
+pub struct ValidateTokenAccount<'info> {
+    /// User who owns the token account
+    pub user: Signer<'info>,
+    
+    /// SPL Token or Token-2022 account to validate
+    /// Anchor's InterfaceAccount<TokenAccount> automatically:
+    /// • Verifies it's owned by one of the two token programs
+    /// • Deserializes the account data
+    /// • Makes fields like mint, owner, amount available
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Optional mint of `token_account`, read for its decimals
+    #[account(constraint = mint.key() == token_account.mint @ BurnerError::MintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+}
+
+// Account context for `simulate_burn_impact` – read-only
+#[derive(Accounts)]
+pub struct SimulateBurnImpact<'info> {
+    /// SPL Token or Token-2022 mint whose supply the burn is measured against
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+}
+
+// Account context for `classify_batch`
+// Accounts to classify are passed as remaining_accounts; nothing is written.
+#[derive(Accounts)]
+pub struct ClassifyBatch<'info> {
+    /// CHECK: wallet the accounts are classified for; read-only and need not sign,
+    /// so UIs can run this before asking for a signature
+    pub user: UncheckedAccount<'info>,
+}
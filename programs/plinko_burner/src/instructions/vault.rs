@@ -0,0 +1,766 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::stake::{
+    self,
+    instruction as stake_instruction,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+use anchor_lang::solana_program::{sysvar, vote};
+use anchor_lang::system_program::{self, Allocate, Assign};
+
+use crate::constants::{MAX_BATCH_ACCOUNTS, MAX_VAULT_STATS, VAULT_STAKE_SEED};
+use crate::errors::BurnerError;
+use crate::instructions::admin::{grow_account, stored_leading_key};
+use crate::state::{BurnerState, ReclaimHistory, VaultAccount};
+
+// Return-data payload of `derive_pdas` – the program's own PDA derivation for a user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PdaView {
+    pub vault: Pubkey,   // ["vault", user]
+    pub vault_bump: u8,
+    pub state: Pubkey,   // ["state"]
+    pub state_bump: u8,
+}
+
+// One `read_vault_stats` entry; MAX_VAULT_STATS depends on its 64-byte size
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultStatsView {
+    pub owner: Pubkey,
+    pub lamports_collected: u64,
+    pub tokens_burned: u128,
+    pub accounts_closed: u64,
+}
+
+// Return-data payload of `vault_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultSummary {
+    pub owner: Pubkey,
+    pub lamports: u64,           // raw balance of the vault account
+    pub withdrawable: u64,       // what withdraw_vault would move right now
+    pub lamports_collected: u64,
+    pub tokens_burned: u128,
+    pub accounts_closed: u64,
+}
+
+pub(crate) fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
+    require!(!ctx.accounts.state.vault_creation_paused, BurnerError::VaultCreationPaused);
+
+    let rent_floor = Rent::get()?.minimum_balance(ctx.accounts.vault.to_account_info().data_len());
+    let vault = &mut ctx.accounts.vault;
+    vault.set_inner(VaultAccount::init(ctx.accounts.user.key(), ctx.bumps.vault, rent_floor));
+
+    msg!("Vault created for user: {}", vault.owner);
+    Ok(())
+}
+
+pub(crate) fn withdraw_vault(ctx: Context<WithdrawVault>) -> Result<()> {
+    ctx.accounts.vault.check_cosigner(ctx.accounts.cosigner.as_ref())?;
+
+    let vault_ai = ctx.accounts.vault.to_account_info();
+    let user_ai  = ctx.accounts.user.to_account_info();
+
+    let withdrawable = ctx.accounts.vault.usable_lamports(&vault_ai)?;
+    require!(
+        withdrawable >= ctx.accounts.state.min_withdraw_lamports,
+        BurnerError::BelowMinWithdraw
+    );
+
+    if withdrawable > 0 {
+        // Only a withdrawal that moves lamports starts the cooldown
+        let now = ctx.accounts.state.now()?;
+        ctx.accounts.vault.record_withdrawal(ctx.accounts.state.withdraw_cooldown_secs, now)?;
+
+        // Manual lamport transfer, PDA → user wallet
+        **vault_ai.try_borrow_mut_lamports()? -= withdrawable;
+        **user_ai.try_borrow_mut_lamports()?  += withdrawable;
+        msg!("Withdrew {} lamports to user", withdrawable);
+    } else {
+        msg!("No lamports to withdraw");
+    }
+    Ok(())
+}
+
+pub(crate) fn withdraw_vault_amount(ctx: Context<WithdrawVaultAmount>, amount: u64) -> Result<()> {
+    ctx.accounts.vault.check_cosigner(ctx.accounts.cosigner.as_ref())?;
+    require!(
+        amount >= ctx.accounts.state.min_withdraw_lamports,
+        BurnerError::BelowMinWithdraw
+    );
+
+    let vault_ai = ctx.accounts.vault.to_account_info();
+    let user_ai  = ctx.accounts.user.to_account_info();
+
+    // Landing exactly on the rent floor is fine, one lamport past it is not
+    require!(
+        amount <= ctx.accounts.vault.usable_lamports(&vault_ai)?,
+        BurnerError::WouldBeUnderRentExempt
+    );
+    let now = ctx.accounts.state.now()?;
+    ctx.accounts.vault.record_withdrawal(ctx.accounts.state.withdraw_cooldown_secs, now)?;
+
+    **vault_ai.try_borrow_mut_lamports()? -= amount;
+    **user_ai.try_borrow_mut_lamports()?  += amount;
+    msg!("Withdrew {} lamports to user", amount);
+    Ok(())
+}
+
+pub(crate) fn batch_close_vaults<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchCloseVaults<'info>>,
+) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), BurnerError::InvalidBatchAccounts);
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_ACCOUNTS, BurnerError::BatchTooLarge);
+
+    let user_key = ctx.accounts.user.key();
+    let cooldown_secs = ctx.accounts.state.withdraw_cooldown_secs;
+    let min_withdraw = ctx.accounts.state.min_withdraw_lamports;
+    let now = ctx.accounts.state.now()?;
+    let mut closed: u32 = 0;
+    let mut lamports_collected: u128 = 0;
+    let mut tokens_burned: u128 = 0;
+    let mut accounts_closed: u64 = 0;
+
+    for vault_info in ctx.remaining_accounts {
+        let mut vault = Account::<VaultAccount>::try_from(vault_info)
+            .map_err(|_| BurnerError::InvalidVaultAccount)?;
+
+        // Security: only the caller's own, canonical vault PDAs
+        require!(vault.owner == user_key, BurnerError::InvalidOwner);
+        let expected = Pubkey::create_program_address(
+            &[b"vault".as_ref(), vault.owner.as_ref(), &[vault.bump]],
+            ctx.program_id,
+        )
+        .map_err(|_| BurnerError::InvalidVaultAccount)?;
+        require_keys_eq!(expected, vault_info.key(), BurnerError::InvalidVaultAccount);
+
+        // Closing pays out everything, so it needs the same cosigner a withdrawal would
+        vault.check_cosigner(ctx.accounts.cosigner.as_ref())?;
+        // ...and it's a withdrawal of the whole balance, so the cooldown holds it back too
+        vault.record_withdrawal(cooldown_secs, now)?;
+        // Rent aside, a vault holding less than the minimum withdrawal stays open
+        require!(vault.usable_lamports(vault_info)? >= min_withdraw, BurnerError::BelowMinWithdraw);
+
+        lamports_collected = lamports_collected
+            .checked_add(vault.lamports_collected as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+        tokens_burned = tokens_burned
+            .checked_add(vault.tokens_burned)
+            .ok_or(BurnerError::MathOverflow)?;
+        accounts_closed = accounts_closed
+            .checked_add(vault.accounts_closed)
+            .ok_or(BurnerError::MathOverflow)?;
+        closed += 1;
+
+        // A repeated entry fails the try_from above, the account is already gone
+        vault.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    // Keep the closed vaults' lifetime stats once their accounts are gone
+    let state = &mut ctx.accounts.state;
+    state.closed_vault_lamports = state
+        .closed_vault_lamports
+        .checked_add(lamports_collected)
+        .ok_or(BurnerError::MathOverflow)?;
+    state.closed_vault_tokens_burned = state
+        .closed_vault_tokens_burned
+        .checked_add(tokens_burned)
+        .ok_or(BurnerError::MathOverflow)?;
+    state.closed_vault_accounts_closed = state
+        .closed_vault_accounts_closed
+        .checked_add(accounts_closed)
+        .ok_or(BurnerError::MathOverflow)?;
+
+    msg!(
+        "Closed {} vaults, {} lamports collected, {} tokens burned and {} accounts closed rolled into state",
+        closed,
+        lamports_collected,
+        tokens_burned,
+        accounts_closed
+    );
+    Ok(())
+}
+
+pub(crate) fn set_cosigner(ctx: Context<SetCosigner>, cosigner: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    // Replacing an existing cosigner needs that cosigner too, or 2-of-2 means nothing
+    vault.check_cosigner(ctx.accounts.current_cosigner.as_ref())?;
+
+    vault.required_cosigner = Some(cosigner);
+    msg!("Vault {} now requires cosigner {}", vault.owner, cosigner);
+    Ok(())
+}
+
+pub(crate) fn clear_cosigner(ctx: Context<ClearCosigner>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.check_cosigner(ctx.accounts.current_cosigner.as_ref())?;
+
+    vault.required_cosigner = None;
+    msg!("Vault {} cosigner cleared", vault.owner);
+    Ok(())
+}
+
+pub(crate) fn set_operator(ctx: Context<SetOperator>, operator: Option<Pubkey>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.operator = operator;
+
+    match operator {
+        Some(operator) => msg!("Vault {} authorized operator {}", vault.owner, operator),
+        None => msg!("Vault {} operator cleared", vault.owner),
+    }
+    Ok(())
+}
+
+pub(crate) fn set_compound_threshold(ctx: Context<SetCompoundThreshold>, threshold: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.compound_threshold = threshold;
+
+    msg!("Vault {} compound threshold set to {} lamports (0 = off)", vault.owner, threshold);
+    Ok(())
+}
+
+pub(crate) fn init_reclaim_history(ctx: Context<InitReclaimHistory>) -> Result<()> {
+    let history = &mut ctx.accounts.history;
+    history.owner = ctx.accounts.user.key();
+    history.bump  = ctx.bumps.history;
+    // history_head, total_records and every slot start zeroed
+
+    msg!("Reclaim history created for {}", history.owner);
+    Ok(())
+}
+
+pub(crate) fn set_strict_mode(ctx: Context<SetStrictMode>, enabled: bool) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.strict_mode = enabled;
+
+    msg!("Vault {} strict mode {}", vault.owner, if enabled { "on" } else { "off" });
+    Ok(())
+}
+
+pub(crate) fn set_auto_withdraw_threshold(ctx: Context<SetAutoWithdrawThreshold>, threshold: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.auto_withdraw_threshold = threshold;
+
+    msg!("Vault {} auto-withdraw above {} lamports (0 = off)", vault.owner, threshold);
+    Ok(())
+}
+
+pub(crate) fn compound_vault(ctx: Context<CompoundVault>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    // Staking moves lamports out just like a withdrawal does
+    vault.check_cosigner(ctx.accounts.cosigner.as_ref())?;
+    require!(vault.compound_threshold > 0, BurnerError::CompoundingDisabled);
+
+    let vault_ai = vault.to_account_info();
+    let amount = vault.usable_lamports(&vault_ai)?;
+    // The stake account's own rent reserve comes out of `amount`; the stake
+    // program rejects whatever is left if it's under the minimum delegation
+    let stake_reserve = Rent::get()?.minimum_balance(StakeStateV2::size_of());
+    require!(
+        amount > vault.compound_threshold && amount > stake_reserve,
+        BurnerError::CompoundBelowThreshold
+    );
+
+    let vault_key = vault.key();
+    let owner = vault.owner;
+    let vault_bump = [vault.bump];
+    let stake_index = vault.stake_count.to_le_bytes();
+    let stake_bump = [ctx.bumps.stake_account];
+    let stake_seeds: &[&[u8]] = &[VAULT_STAKE_SEED, vault_key.as_ref(), &stake_index, &stake_bump];
+    let vault_seeds: &[&[u8]] = &[b"vault".as_ref(), owner.as_ref(), &vault_bump];
+
+    // The vault is program-owned, so it can be debited directly; the stake
+    // address is still a plain system account until the assign below
+    let stake_ai = ctx.accounts.stake_account.to_account_info();
+    **vault_ai.try_borrow_mut_lamports()? -= amount;
+    **stake_ai.try_borrow_mut_lamports()? += amount;
+
+    let system_ai = ctx.accounts.system_program.to_account_info();
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_ai.clone(),
+            Allocate { account_to_allocate: stake_ai.clone() },
+            &[stake_seeds],
+        ),
+        StakeStateV2::size_of() as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_ai,
+            Assign { account_to_assign: stake_ai.clone() },
+            &[stake_seeds],
+        ),
+        &stake::program::ID,
+    )?;
+
+    // Staker is the vault PDA so the program can delegate; withdrawer is the
+    // owner, who can reassign the staker and unstake without this program
+    let authorized = Authorized {
+        staker: vault_key,
+        withdrawer: owner,
+    };
+    invoke(
+        &stake_instruction::initialize(&stake_ai.key(), &authorized, &Lockup::default()),
+        &[stake_ai.clone(), ctx.accounts.rent.to_account_info()],
+    )?;
+    invoke_signed(
+        &stake_instruction::delegate_stake(&stake_ai.key(), &vault_key, &ctx.accounts.vote_account.key()),
+        &[
+            stake_ai.clone(),
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            vault_ai,
+        ],
+        &[vault_seeds],
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.stake_count = vault.stake_count.checked_add(1).ok_or(BurnerError::MathOverflow)?;
+
+    msg!(
+        "Staked {} lamports from vault {} to {} via stake account {}",
+        amount,
+        vault_key,
+        ctx.accounts.vote_account.key(),
+        stake_ai.key()
+    );
+    Ok(())
+}
+
+pub(crate) fn refresh_rent_floor(ctx: Context<RefreshRentFloor>) -> Result<()> {
+    let data_len = ctx.accounts.vault.to_account_info().data_len();
+    let vault = &mut ctx.accounts.vault;
+
+    let live_floor = Rent::get()?.minimum_balance(data_len);
+    msg!("Vault rent floor snapshot: {} -> {} lamports", vault.rent_floor_snapshot, live_floor);
+    vault.rent_floor_snapshot = live_floor;
+    Ok(())
+}
+
+pub(crate) fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+    let usable = ctx.accounts.vault.usable_lamports(&ctx.accounts.vault.to_account_info())?;
+    let vault = &mut ctx.accounts.vault;
+
+    // Picks up lamports that arrived outside the program (airdrops, plain transfers)
+    // and drops ones already withdrawn; the rent floor is never counted
+    msg!("Reconciled vault tally: {} -> {} lamports", vault.lamports_collected, usable);
+    vault.lamports_collected = usable;
+    Ok(())
+}
+
+pub(crate) fn repair_vault(ctx: Context<RepairVault>) -> Result<()> {
+    let live_floor = Rent::get()?.minimum_balance(ctx.accounts.vault.to_account_info().data_len());
+    let owner = ctx.accounts.user.key();
+    let vault = &mut ctx.accounts.vault;
+    let mut repaired = false;
+
+    // The seeds already proved this is the signer's canonical vault, so the
+    // address is the source of truth for both fields; lamports are left alone
+    if vault.bump != ctx.bumps.vault {
+        msg!("Vault bump repaired: {} -> {}", vault.bump, ctx.bumps.vault);
+        vault.bump = ctx.bumps.vault;
+        repaired = true;
+    }
+    if vault.owner != owner {
+        msg!("Vault owner repaired: {} -> {}", vault.owner, owner);
+        vault.owner = owner;
+        repaired = true;
+    }
+    if vault.rent_floor_snapshot == 0 {
+        msg!("Vault rent floor snapshot repaired: 0 -> {}", live_floor);
+        vault.rent_floor_snapshot = live_floor;
+        repaired = true;
+    }
+
+    if !repaired {
+        msg!("Vault {} is consistent, nothing to repair", owner);
+    }
+    Ok(())
+}
+
+pub(crate) fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+    let info = ctx.accounts.vault.to_account_info();
+    let owner = stored_leading_key(&info, VaultAccount::DISCRIMINATOR)?;
+    require_keys_eq!(owner, ctx.accounts.user.key(), BurnerError::InvalidOwner);
+
+    // Like `migrate_state_bump`: every field since the first three went on the end,
+    // so growing keeps what's stored and the zeroed tail reads as off / unset
+    let old_len = info.data_len();
+    let new_len = 8 + VaultAccount::INIT_SPACE;
+    grow_account(
+        &info,
+        new_len,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut vault = VaultAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    vault.bump = ctx.bumps.vault;
+    if vault.rent_floor_snapshot == 0 {
+        vault.rent_floor_snapshot = Rent::get()?.minimum_balance(new_len);
+    }
+    vault.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    msg!("Vault of {} migrated, account {} -> {} bytes", owner, old_len, new_len);
+    Ok(())
+}
+
+pub(crate) fn derive_pdas(ctx: Context<DerivePdas>, user: Pubkey) -> Result<PdaView> {
+    // Same seeds as the account contexts use, so clients can't drift from them
+    let (vault, vault_bump) = Pubkey::find_program_address(&[b"vault", user.as_ref()], ctx.program_id);
+    let (state, state_bump) = Pubkey::find_program_address(&[b"state"], ctx.program_id);
+
+    Ok(PdaView {
+        vault,
+        vault_bump,
+        state,
+        state_bump,
+    })
+}
+
+pub(crate) fn read_vault_stats(ctx: Context<ReadVaultStats>) -> Result<Vec<VaultStatsView>> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_VAULT_STATS,
+        BurnerError::TooManyVaultStats
+    );
+
+    let mut stats = Vec::with_capacity(ctx.remaining_accounts.len());
+    for vault_info in ctx.remaining_accounts {
+        require_keys_eq!(*vault_info.owner, *ctx.program_id, BurnerError::InvalidVaultAccount);
+        let vault = VaultAccount::try_deserialize(&mut &vault_info.try_borrow_data()?[..])
+            .map_err(|_| BurnerError::InvalidVaultAccount)?;
+
+        // Only canonical vault PDAs, so a leaderboard can't be fed look-alike accounts
+        let expected = Pubkey::create_program_address(
+            &[b"vault".as_ref(), vault.owner.as_ref(), &[vault.bump]],
+            ctx.program_id,
+        )
+        .map_err(|_| BurnerError::InvalidVaultAccount)?;
+        require_keys_eq!(expected, vault_info.key(), BurnerError::InvalidVaultAccount);
+
+        stats.push(VaultStatsView {
+            owner: vault.owner,
+            lamports_collected: vault.lamports_collected,
+            tokens_burned: vault.tokens_burned,
+            accounts_closed: vault.accounts_closed,
+        });
+    }
+
+    msg!("Read stats for {} vaults", stats.len());
+    Ok(stats)
+}
+
+pub(crate) fn vault_summary(ctx: Context<ReadVaultSummary>) -> Result<VaultSummary> {
+    let vault = &ctx.accounts.vault;
+    let vault_ai = vault.to_account_info();
+
+    Ok(VaultSummary {
+        owner: vault.owner,
+        lamports: vault_ai.lamports(),
+        // Same rent floor withdraw_vault keeps back, so the two never disagree
+        withdrawable: vault.usable_lamports(&vault_ai)?,
+        lamports_collected: vault.lamports_collected,
+        tokens_burned: vault.tokens_burned,
+        accounts_closed: vault.accounts_closed,
+    })
+}
+
+// Account context for `create_vault`
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>, // wallet creating the vault
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + VaultAccount::INIT_SPACE,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, VaultAccount>, // vault PDA derived from ("vault", user)
+
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read for vault_creation_paused
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `withdraw_vault`
+#[derive(Accounts)]
+pub struct WithdrawVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read for min_withdraw_lamports and withdraw_cooldown_secs
+
+    pub cosigner: Option<Signer<'info>>, // required when the vault has a required_cosigner
+}
+
+// Account context for `withdraw_vault_amount`
+#[derive(Accounts)]
+pub struct WithdrawVaultAmount<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+
+    #[account(seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>, // read for min_withdraw_lamports and withdraw_cooldown_secs
+
+    pub cosigner: Option<Signer<'info>>, // required when the vault has a required_cosigner
+}
+
+// Account context for `batch_close_vaults`
+// Vault PDAs to close are passed as remaining_accounts; each is checked in the handler.
+#[derive(Accounts)]
+pub struct BatchCloseVaults<'info> {
+    /// Owner of every vault in the batch; receives their lamports
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Global state PDA, keeps the closed vaults' stats
+    #[account(mut, seeds = [b"state"], bump = state.state_bump)]
+    pub state: Account<'info, BurnerState>,
+
+    pub cosigner: Option<Signer<'info>>, // required when any vault has a required_cosigner
+}
+
+// Account context for `set_cosigner`
+#[derive(Accounts)]
+pub struct SetCosigner<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+
+    pub current_cosigner: Option<Signer<'info>>, // required when a cosigner is already set
+}
+
+// Account context for `clear_cosigner`
+#[derive(Accounts)]
+pub struct ClearCosigner<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+
+    pub current_cosigner: Option<Signer<'info>>, // required when a cosigner is set
+}
+
+// Account context for `set_operator`
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `set_compound_threshold`
+#[derive(Accounts)]
+pub struct SetCompoundThreshold<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `init_reclaim_history`
+#[derive(Accounts)]
+pub struct InitReclaimHistory<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>, // owner of the history, pays its rent
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + ReclaimHistory::INIT_SPACE,
+        seeds = [b"history", user.key().as_ref()],
+        bump
+    )]
+    pub history: Box<Account<'info, ReclaimHistory>>, // boxed: ~1 KB of ring buffer
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `set_strict_mode`
+#[derive(Accounts)]
+pub struct SetStrictMode<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `set_auto_withdraw_threshold`
+#[derive(Accounts)]
+pub struct SetAutoWithdrawThreshold<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `compound_vault`
+#[derive(Accounts)]
+pub struct CompoundVault<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner; the stake's staker
+
+    /// CHECK: the vault's next stake account, still unallocated; created and handed to the stake program here
+    #[account(
+        mut,
+        seeds = [VAULT_STAKE_SEED, vault.key().as_ref(), &vault.stake_count.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: validator vote account to delegate to; the stake program validates it
+    #[account(owner = vote::program::ID)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: clock sysvar, passed through to the stake program
+    #[account(address = sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: stake history sysvar, passed through unread (it's too large to deserialize here)
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: legacy stake config account, unused but still required by DelegateStake
+    #[account(address = stake::config::ID)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>, // required by the stake program's Initialize
+
+    /// CHECK: native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub cosigner: Option<Signer<'info>>, // required when the vault has a required_cosigner
+}
+
+// Account context for `refresh_rent_floor`
+#[derive(Accounts)]
+pub struct RefreshRentFloor<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `reconcile_vault`
+#[derive(Accounts)]
+pub struct ReconcileVault<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
+    )]
+    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
+}
+
+// Account context for `repair_vault`
+#[derive(Accounts)]
+pub struct RepairVault<'info> {
+    pub user: Signer<'info>,
+
+    /// Caller's vault; found by canonical bump and no stored fields, since those are what's repaired
+    #[account(mut, seeds = [b"vault", user.key().as_ref()], bump)]
+    pub vault: Account<'info, VaultAccount>,
+}
+
+// Account context for `migrate_vault`
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// Vault owner; pays for growing it
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: may still be on an older, shorter layout that doesn't deserialize;
+    /// owner, discriminator and stored owner are checked in the handler
+    #[account(mut, seeds = [b"vault", user.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// System program (tops up the rent of the grown account)
+    pub system_program: Program<'info, System>,
+}
+
+// Account context for `derive_pdas` – pure computation, no accounts
+#[derive(Accounts)]
+pub struct DerivePdas {}
+
+// Account context for `vault_summary`
+#[derive(Accounts)]
+pub struct ReadVaultSummary<'info> {
+    #[account(seeds = [b"vault", vault.owner.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, VaultAccount>, // any canonical vault PDA, read-only
+}
+
+// Account context for `read_vault_stats`
+// Vault PDAs to read are passed as remaining_accounts; nothing is written.
+#[derive(Accounts)]
+pub struct ReadVaultStats {}
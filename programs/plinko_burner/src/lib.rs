@@ -1,5 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, CloseAccount, close_account, Burn, burn};
+
+pub mod constants;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod logs;
+pub mod state;
+
+pub use constants::*;
+pub use errors::*;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
 
 declare_id!("Cz4m7mpWX6nSUZxfKp2vjnHgYdF5rx9fmEwe9fWrabXd"); 
 
@@ -11,129 +23,301 @@ pub mod token_burner {
     /// * authority  – wallet that governs future upgrades or admin ops
     /// * state PDA – stores config + timestamp
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let state = &mut ctx.accounts.state;           // mutable alias to PDA
-        let clock = Clock::get()?;                     // current cluster time
+        instructions::admin::initialize(ctx)
+    }
 
-        // Populate persistent fields
-        state.authority      = ctx.accounts.authority.key(); //admin address
-        state.is_initialized = true;                   // sanity flag
-        state.created_at     = clock.unix_timestamp;   // cluster time
+    /// Returns the key config fields as a compact, versioned `ConfigView`.
+    /// Read-only: clients call it via simulation and decode the return data
+    /// instead of deserializing the raw `BurnerState` layout.
+    pub fn get_config(ctx: Context<GetConfig>) -> Result<ConfigView> {
+        instructions::admin::get_config(ctx)
+    }
 
-        msg!("Token Burner initialized with authority: {}", state.authority);
-        Ok(())
+    /// Returns the headline stats of a public stats page in one call, as a versioned
+    /// `DashboardView` (byte layout documented on the struct). Read-only, like `get_config`.
+    /// Burned, closed and rent totals cover what `fold_shards` has folded so far.
+    pub fn global_dashboard(ctx: Context<GlobalDashboard>) -> Result<DashboardView> {
+        instructions::admin::global_dashboard(ctx)
     }
 
-    /// Creates a vault PDA so the caller can later receive rent refunds.
-    pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
+    /// Returns the deployed crate version as a numeric `major.minor.patch` triple plus
+    /// the state account's schema `version`, so monitoring can check both in one call.
+    pub fn program_version(ctx: Context<ProgramVersionView>) -> Result<ProgramVersion> {
+        instructions::admin::program_version(ctx)
+    }
 
-        vault.owner              = ctx.accounts.user.key(); // vault owner
-        vault.bump               = ctx.bumps.vault;         // PDA bump
-        vault.lamports_collected = 0;                       // optional tally
+    /// Returns a `u64` of `FEATURE_*` bits describing this deployment: bits 0-15 are
+    /// built-in capabilities, 16-31 compile-time features, 32+ options switched on in
+    /// the current config. Read-only, for clients to enable or hide UI.
+    pub fn supported_features(ctx: Context<SupportedFeatures>) -> Result<u64> {
+        instructions::admin::supported_features(ctx)
+    }
 
-        msg!("Vault created for user: {}", vault.owner);
-        Ok(())
+    /// Creates a vault PDA so the caller can later receive rent refunds.
+    pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
+        instructions::vault::create_vault(ctx)
     }
 
     /// Withdraws lamports above the rent‑exempt minimum from the vault to the caller.
+    /// The minimum is `max(rent_floor_snapshot, live rent)`, see `refresh_rent_floor`.
+    /// If the vault has a `required_cosigner`, that key must sign as `cosigner` too.
+    /// Fails with `BelowMinWithdraw` if the amount is under `min_withdraw_lamports`.
     pub fn withdraw_vault(ctx: Context<WithdrawVault>) -> Result<()> {
-        let vault_ai = ctx.accounts.vault.to_account_info();
-        let user_ai  = ctx.accounts.user.to_account_info();
+        instructions::vault::withdraw_vault(ctx)
+    }
+
+    /// Withdraws exactly `amount` lamports from the vault to the caller, leaving the
+    /// rest in place. Fails with `WouldBeUnderRentExempt` if that would take the vault
+    /// below its rent floor; cosigner and `min_withdraw_lamports` rules as in `withdraw_vault`.
+    pub fn withdraw_vault_amount(ctx: Context<WithdrawVaultAmount>, amount: u64) -> Result<()> {
+        instructions::vault::withdraw_vault_amount(ctx, amount)
+    }
+
+    /// Closes every vault PDA passed as a remaining account, sending all of its
+    /// lamports (rent included) to the caller.
+    /// * Each vault must be the caller's own canonical `["vault", owner]` PDA
+    /// * Vaults with a `required_cosigner` need it to sign as `cosigner`
+    /// * Closing counts as a withdrawal: a vault still inside `withdraw_cooldown_secs`
+    ///   fails the batch with `WithdrawCooldownActive`
+    /// * Likewise each vault's `usable_lamports` must reach `min_withdraw_lamports`,
+    ///   else `BelowMinWithdraw`
+    /// * Their `lamports_collected`, `tokens_burned` and `accounts_closed` are added to
+    ///   `BurnerState`'s `closed_vault_lamports`, `closed_vault_tokens_burned` and
+    ///   `closed_vault_accounts_closed`
+    /// * At most `MAX_BATCH_ACCOUNTS` vaults, else `BatchTooLarge`
+    pub fn batch_close_vaults<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchCloseVaults<'info>>,
+    ) -> Result<()> {
+        instructions::vault::batch_close_vaults(ctx)
+    }
+
+    /// Makes `cosigner` a required second signer on the caller's vault withdrawals.
+    /// Replacing an existing cosigner needs the current one to sign as well.
+    pub fn set_cosigner(ctx: Context<SetCosigner>, cosigner: Pubkey) -> Result<()> {
+        instructions::vault::set_cosigner(ctx, cosigner)
+    }
+
+    /// Returns the caller's vault to single-sig; the current cosigner must sign.
+    pub fn clear_cosigner(ctx: Context<ClearCosigner>) -> Result<()> {
+        instructions::vault::clear_cosigner(ctx)
+    }
+
+    /// Approves (or with `None`, revokes) the relayer allowed to take an operator fee
+    /// in `relayed_burn_and_close` on the caller's behalf.
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Option<Pubkey>) -> Result<()> {
+        instructions::vault::set_operator(ctx, operator)
+    }
+
+    /// Opts the caller's vault into `compound_vault` (or out of it, with 0).
+    /// * `threshold` – usable lamports the vault must hold before compounding is allowed
+    pub fn set_compound_threshold(ctx: Context<SetCompoundThreshold>, threshold: u64) -> Result<()> {
+        instructions::vault::set_compound_threshold(ctx, threshold)
+    }
 
-        let rent_floor = Rent::get()?.minimum_balance(vault_ai.data_len());
-        let withdrawable = vault_ai.lamports().saturating_sub(rent_floor);
+    /// Creates the caller's `ReclaimHistory` PDA: a fixed ring of the last
+    /// `RECLAIM_HISTORY_LEN` (16) closes, queryable on-chain without an indexer.
+    /// Closes only write to it when it is passed; the user pays ~0.0075 SOL rent once.
+    pub fn init_reclaim_history(ctx: Context<InitReclaimHistory>) -> Result<()> {
+        instructions::vault::init_reclaim_history(ctx)
+    }
+
+    /// Turns the caller's strict mode on or off. While on, `burn_and_close_token_account`
+    /// refuses mints that still have a freeze authority unless called with `force`.
+    pub fn set_strict_mode(ctx: Context<SetStrictMode>, enabled: bool) -> Result<()> {
+        instructions::vault::set_strict_mode(ctx, enabled)
+    }
+
+    /// Sets the caller's auto-withdraw threshold: whenever a `close_token_account` the
+    /// owner signs leaves more than `threshold` usable lamports in the vault, the excess
+    /// goes straight to the owner's wallet in the same instruction. 0 (the default) turns
+    /// it off. Skipped, never failing the close, while a co-signer is required, the
+    /// withdraw cooldown is running or the excess is under the minimum withdrawal.
+    pub fn set_auto_withdraw_threshold(ctx: Context<SetAutoWithdrawThreshold>, threshold: u64) -> Result<()> {
+        instructions::vault::set_auto_withdraw_threshold(ctx, threshold)
+    }
+
+    /// Stakes the vault's usable lamports with the native stake program once they exceed
+    /// the vault's `compound_threshold`, turning idle rent into yield.
+    /// * Opens a fresh stake account per call at `["vault_stake", vault, stake_count LE]`,
+    ///   funded straight from the vault; its rent reserve comes out of the staked amount
+    /// * The vault PDA is the staker and signs the delegation; the vault owner is the
+    ///   withdrawer, so they can reassign the staker, deactivate and withdraw directly
+    ///   through the stake program without going through this one
+    /// * The stake program enforces its minimum delegation and validates `vote_account`
+    /// * Needs the vault's cosigner like a withdrawal; `lamports_collected` is unchanged
+    pub fn compound_vault(ctx: Context<CompoundVault>) -> Result<()> {
+        instructions::vault::compound_vault(ctx)
+    }
+
+    /// Re-captures the vault's `rent_floor_snapshot` from the live rent sysvar.
+    /// Withdrawals reserve the larger of the snapshot and live rent, so a stale
+    /// snapshot only ever over-reserves; refresh it after rent is lowered.
+    pub fn refresh_rent_floor(ctx: Context<RefreshRentFloor>) -> Result<()> {
+        instructions::vault::refresh_rent_floor(ctx)
+    }
 
-        if withdrawable > 0 {
-            // Manual lamport transfer, PDA → user wallet
-            **vault_ai.try_borrow_mut_lamports()? -= withdrawable;
-            **user_ai.try_borrow_mut_lamports()?  += withdrawable;
-            msg!("Withdrew {} lamports to user", withdrawable);
-        } else {
-            msg!("No lamports to withdraw");
-        }
-        Ok(())
+    /// Recovery tool for a vault left inconsistent, e.g. by a creation that stopped
+    /// between `init` and filling in its fields. The vault is located by the signer's
+    /// canonical PDA alone, then `bump` and `owner` are re-derived from it and a zero
+    /// `rent_floor_snapshot` is refilled from the live rent. Lamports are never moved.
+    pub fn repair_vault(ctx: Context<RepairVault>) -> Result<()> {
+        instructions::vault::repair_vault(ctx)
+    }
+
+    /// Grows a vault created on an older, shorter `VaultAccount` layout to the current
+    /// size, so the typed contexts can load it again; the owner signs and pays the extra
+    /// rent. Stored fields are kept, newer ones start zeroed (off / unset), and the bump
+    /// and a missing `rent_floor_snapshot` are filled in. A current vault is left as is.
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::vault::migrate_vault(ctx)
+    }
+
+    /// Resets the vault's `lamports_collected` to its actual balance minus the rent floor.
+    /// Use after lamports reached the vault outside the program, e.g. a direct transfer.
+    pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+        instructions::vault::reconcile_vault(ctx)
+    }
+
+    /// Returns one vault's `owner`, raw `lamports`, `withdrawable` balance, and its
+    /// `lamports_collected`, `tokens_burned` and `accounts_closed` tallies in a single
+    /// `VaultSummary`. `withdrawable` uses the same
+    /// rent floor as `withdraw_vault`. Read-only, no signer needed.
+    pub fn vault_summary(ctx: Context<ReadVaultSummary>) -> Result<VaultSummary> {
+        instructions::vault::vault_summary(ctx)
+    }
+
+    /// Returns `owner`, `lamports_collected`, `tokens_burned` and `accounts_closed` for
+    /// each vault PDA passed as remaining accounts, in order. Read-only, meant to be
+    /// simulated by dashboards.
+    /// * At most `MAX_VAULT_STATS` (15) vaults per call, what fits in return data;
+    ///   page through larger sets
+    /// * Fails with `InvalidVaultAccount` on anything that isn't a canonical vault PDA
+    pub fn read_vault_stats(ctx: Context<ReadVaultStats>) -> Result<Vec<VaultStatsView>> {
+        instructions::vault::read_vault_stats(ctx)
+    }
+
+    /// Returns the vault and state PDAs (and bumps) for `user` as the program derives them.
+    /// Read-only and account-free: clients simulate it instead of hard-coding seeds.
+    pub fn derive_pdas(ctx: Context<DerivePdas>, user: Pubkey) -> Result<PdaView> {
+        instructions::vault::derive_pdas(ctx, user)
     }
 
     /// Validates a single token account for future burning/closing.
     /// * Checks ownership matches the signer
-    /// * Verifies it's a real SPL token account  
+    /// * Verifies it's a real SPL Token or Token-2022 account
     /// * Logs basic account info
+    /// * Emits a `ValidationReport`, with the mint's decimals if the optional mint is passed
+    ///   and whether the account has Token-2022's immutable-owner extension
     pub fn validate_token_account(ctx: Context<ValidateTokenAccount>) -> Result<()> {
-        let token_account = &ctx.accounts.token_account;
-        let user = &ctx.accounts.user;
-        
-        // Security: Verify the token account owner matches the signer
-        require!(
-            token_account.owner == user.key(),
-            BurnerError::UnauthorizedAccount
-        );
-        
-        // Log account details for debugging
-        msg!(
-            "Valid token account - Mint: {}, Balance: {}, Owner: {}",
-            token_account.mint,
-            token_account.amount,
-            token_account.owner
-        );
-        
-        // Check if account is empty (will be useful in later stages)
-        if token_account.amount == 0 {
-            msg!("Token account is empty and ready to close");
-        } else {
-            msg!("Token account has {} tokens", token_account.amount);
-        }
-        
-        Ok(())
-    }
-
-    /// Closes an empty SPL token account and sends the rent to the user's vault.
+        instructions::validate::validate_token_account(ctx)
+    }
+
+    /// Buckets every remaining account for `user`, returning one `CLASS_*` byte per
+    /// account, in order, as a Borsh `Vec<u8>` in return data:
+    /// * 0 `CLASS_WRONG_OWNER` – not a token account of `user`, skip
+    /// * 1 `CLASS_FROZEN` – needs a thaw first
+    /// * 2 `CLASS_BURNABLE` – has a balance, burn + close
+    /// * 3 `CLASS_EMPTY` – zero balance, close
+    /// Up to `MAX_BATCH_ACCOUNTS` accounts per call.
+    pub fn classify_batch(ctx: Context<ClassifyBatch>) -> Result<Vec<u8>> {
+        instructions::validate::classify_batch(ctx)
+    }
+
+    /// `classify_batch` plus each account's current lamports, read straight from its
+    /// `AccountInfo`, so a UI can show exactly what closing each one reclaims. Returns a
+    /// Borsh `Vec<ClassifiedAccount>`; at 9 bytes per entry the `MAX_BATCH_ACCOUNTS`
+    /// cap (436 bytes total) keeps it under the 1024-byte return data limit.
+    pub fn classify_batch_with_rent(ctx: Context<ClassifyBatch>) -> Result<Vec<ClassifiedAccount>> {
+        instructions::validate::classify_batch_with_rent(ctx)
+    }
+
+    /// Full per-account details for a pre-burn confirmation screen: a Borsh
+    /// `Vec<DetailedAccount>` of `{ pubkey, mint, amount, owner_matches }`, one per
+    /// remaining account, in order. Every entry must be an SPL Token or Token-2022
+    /// account (`NotATokenAccount` otherwise); a wrong owner only clears `owner_matches`.
+    /// Up to `MAX_DETAILED_ACCOUNTS` (13) accounts per call, to fit in return data.
+    pub fn validate_batch_detailed(ctx: Context<ClassifyBatch>) -> Result<Vec<DetailedAccount>> {
+        instructions::validate::validate_batch_detailed(ctx)
+    }
+
+    /// Previews a burn of `amount` raw tokens against `mint`'s current supply,
+    /// returning the resulting supply and the reduction in bps as a `BurnImpact`.
+    /// Read-only; fails with `BurnExceedsSupply` if `amount` is more than exists.
+    pub fn simulate_burn_impact(ctx: Context<SimulateBurnImpact>, amount: u64) -> Result<BurnImpact> {
+        instructions::validate::simulate_burn_impact(ctx, amount)
+    }
+
+    /// Unwraps a wSOL account: closes it straight into the user's wallet, since both the
+    /// wrapped principal and the rent are the user's own SOL (no vault involved).
+    /// * Logs the principal in SOL (9 decimals) and emits `WsolUnwrapped` with the two
+    ///   amounts apart; an already-empty account just closes with a principal of 0
+    /// * Built with `event-cpi`, the event is a self-CPI and the call takes `event_authority`
+    ///   and `program`
+    pub fn unwrap_and_close_wsol(ctx: Context<UnwrapAndCloseWsol>) -> Result<()> {
+        instructions::close::unwrap_and_close_wsol(ctx)
+    }
+
+    /// Closes an empty SPL Token or Token-2022 account and sends the rent to the user's vault.
     /// Designed with ALT support in mind for batch operations in future stages.
-    /// * Verifies the token account is empty (0 balance)
+    /// * Verifies the token account is empty (0 balance), or holds at most
+    ///   `dust_threshold` – that dust is burned first and needs the optional `mint`
     /// * Closes the account using SPL Token program
     /// * Rent lamports are sent to the user's vault PDA
+    /// * Optional `history` – the owner's `ReclaimHistory`, which gets a record of the close
+    /// * Optional `instructions` – the instructions sysvar, required while the duplicate
+    ///   close check (`set_duplicate_close_check`) is on
     pub fn close_token_account(ctx: Context<CloseTokenAccount>) -> Result<()> {
-        let token_account = &ctx.accounts.token_account;
-        let user = &ctx.accounts.user;
-        
-        // Security: Verify the token account owner matches the signer
-        require!(
-            token_account.owner == user.key(),
-            BurnerError::UnauthorizedAccount
-        );
-        
-        // Verify the token account is empty
-        require!(
-            token_account.amount == 0,
-            BurnerError::AccountNotEmpty
-        );
-        
-        msg!(
-            "Closing token account - Mint: {}, Owner: {}",
-            token_account.mint,
-            token_account.owner
-        );
-        
-        // Create CPI context for closing the token account
-        let cpi_accounts = CloseAccount {
-            account: ctx.accounts.token_account.to_account_info(),
-            destination: ctx.accounts.vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        
-        // Close the token account - rent goes to vault
-        close_account(cpi_ctx)?;
-        
-        // Update vault lamports collected (optional tracking)
-        let vault = &mut ctx.accounts.vault;
-        let rent = Rent::get()?;
-        let rent_lamports = rent.minimum_balance(TokenAccount::LEN);
-        vault.lamports_collected = vault.lamports_collected.saturating_add(rent_lamports);
-        
-        msg!("Token account closed successfully, {} lamports sent to vault", rent_lamports);
-        Ok(())
+        instructions::close::close_token_account(ctx)
+    }
+
+    /// Pauses (or resumes) creating new vaults, e.g. during a migration or incident.
+    /// Admin or `ROLE_OPS`. Covers `create_vault` and the lazily-creating
+    /// `close_with_vault_init` / `relayed_burn_and_close`; existing vaults are unaffected.
+    pub fn set_vault_creation_paused(ctx: Context<SetVaultCreationPaused>, paused: bool) -> Result<()> {
+        instructions::admin::set_vault_creation_paused(ctx, paused)
+    }
+
+    /// Pauses or resumes every burn; closes of empty accounts keep working.
+    /// Resuming also clears a tripped circuit breaker and starts its window afresh.
+    /// Admin or `ROLE_OPS`.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::admin::set_paused(ctx, paused)
+    }
+
+    /// Turns on (or off) rejecting a `close_token_account` / `burn_and_close_token_account`
+    /// whose token account another such instruction in the same transaction also closes,
+    /// with `DuplicateInTransaction`. Off by default, since callers then have to pass the
+    /// instructions sysvar and every close pays to scan the whole transaction.
+    /// Admin or `ROLE_OPS`.
+    pub fn set_duplicate_close_check(ctx: Context<SetDuplicateCloseCheck>, enabled: bool) -> Result<()> {
+        instructions::admin::set_duplicate_close_check(ctx, enabled)
+    }
+
+    /// Cheapest close: the owner and emptiness checks and the CloseAccount CPI into the
+    /// user's vault, nothing else. No state account, no dust burn, no events or logs.
+    /// * A non-empty account fails with `AccountNotEmpty`, wSOL included
+    /// * Lean closes don't count towards any stats: the vault's tallies and the
+    ///   global counters are left as they are
+    /// * `vault_bump` – the vault's stored bump, so the PDA check skips the bump search
+    /// * The token account is parsed by hand rather than typed; anything that isn't a
+    ///   token account of `token_program` fails with `NotATokenAccount`
+    pub fn close_token_account_lean(ctx: Context<CloseTokenAccountLean>, vault_bump: u8) -> Result<()> {
+        instructions::close::close_token_account_lean(ctx, vault_bump)
+    }
+
+    /// Closes an empty token account with the rent destination picked per call, instead
+    /// of always the vault: `destination` must be the owner's vault PDA (stats are
+    /// updated) or the owner's own wallet, else `InvalidRentDestination`.
+    pub fn close_token_account_to(ctx: Context<CloseTokenAccountTo>) -> Result<()> {
+        instructions::close::close_token_account_to(ctx)
+    }
+
+    /// One-step variant of `close_token_account` for users without a vault yet.
+    /// * Creates the vault PDA if needed, paid by the user (~0.0012 SOL rent,
+    ///   recoverable only down to the vault's rent floor via `withdraw_vault`)
+    /// * Then closes the empty token account into it exactly like `close_token_account`
+    pub fn close_with_vault_init(ctx: Context<CloseWithVaultInit>) -> Result<()> {
+        instructions::close::close_with_vault_init(ctx)
     }
 
     /// Burns all tokens in an account and then closes it.
@@ -141,225 +325,547 @@ pub mod token_burner {
     /// * Burns all tokens in the account to reduce total supply
     /// * Closes the empty account and sends rent to user's vault
     /// * Designed with ALT support in mind for batch operations
-    pub fn burn_and_close_token_account(ctx: Context<BurnAndCloseTokenAccount>) -> Result<()> {
-        let token_account = &ctx.accounts.token_account;
-        let user = &ctx.accounts.user;
-        
-        // Security: Verify the token account owner matches the signer
-        require!(
-            token_account.owner == user.key(),
-            BurnerError::UnauthorizedAccount
-        );
-        
-        let token_amount = token_account.amount;
-        
-        msg!(
-            "Burning and closing token account - Mint: {}, Amount: {}, Owner: {}",
-            token_account.mint,
-            token_amount,
-            token_account.owner
-        );
-        
-        // Only burn if there are tokens to burn
-        if token_amount > 0 {
-            // Create CPI context for burning tokens
-            let burn_accounts = Burn {
-                mint: ctx.accounts.mint.to_account_info(),
-                from: ctx.accounts.token_account.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            };
-            
-            let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
-            
-            // Burn all tokens in the account
-            burn(burn_ctx, token_amount)?;
-            
-            msg!("Burned {} tokens from mint {}", token_amount, token_account.mint);
-        } else {
-            msg!("No tokens to burn, proceeding to close account");
-        }
-        
-        // Create CPI context for closing the token account
-        let close_accounts = CloseAccount {
-            account: ctx.accounts.token_account.to_account_info(),
-            destination: ctx.accounts.vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        
-        let close_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), close_accounts);
-        
-        // Close the token account - rent goes to vault
-        close_account(close_ctx)?;
-        
-        // Update vault lamports collected (optional tracking)
-        let vault = &mut ctx.accounts.vault;
-        let rent = Rent::get()?;
-        let rent_lamports = rent.minimum_balance(TokenAccount::LEN);
-        vault.lamports_collected = vault.lamports_collected.saturating_add(rent_lamports);
-        
-        msg!(
-            "Burned {} tokens and closed account successfully, {} lamports sent to vault",
-            token_amount,
-            rent_lamports
-        );
-        Ok(())
+    /// * Rejects burns above `max_supply_pct_bps` of the mint's supply unless `force` is set
+    /// * In a strict-mode vault, also rejects freezable mints unless `force` is set
+    /// * Rejects the mints in `PROTECTED_MINTS` (wrapped SOL) with `ProtectedSystemMint`
+    ///   unless `force` is set
+    /// * `deadline` – Unix timestamp after which the call fails with `DeadlineExceeded`,
+    ///   so a delayed transaction can't execute late; 0 disables the check
+    /// * `strict_empty` – close only: fails with `AccountNotEmpty` instead of burning a
+    ///   nonzero balance
+    /// * Optional `epoch_stats` – the current epoch's `EpochStats` rollup, created by the first
+    ///   burn of the epoch that passes it (the user pays ~0.0012 SOL rent for it)
+    /// * Optional `history` – the user's `ReclaimHistory`, which gets a record of the close
+    /// * Optional `metadata` – the mint's Metaplex metadata; its name (cut to
+    ///   `MAX_EVENT_NAME_LEN` bytes) goes into the `TokenBurned` event, which is empty without it
+    /// * While a token fee is set (`set_token_fee`), first transfers it from `fee_source`
+    ///   to `fee_treasury`; the four `fee_*` accounts are then required, unused otherwise
+    /// * A frozen account, e.g. one opened frozen by a Token-2022 `DefaultAccountState` mint,
+    ///   is thawed first when the mint's freeze authority co-signs as optional `freeze_authority`;
+    ///   without it the call fails with `DefaultFrozenAccount` or `AccountFrozen`, and with
+    ///   `FrozenWithoutFreezeAuthority` if the mint can no longer thaw at all
+    /// * Optional `instructions` – the instructions sysvar, required while the duplicate
+    ///   close check (`set_duplicate_close_check`) is on
+    /// * Built with `event-cpi`, `TokenBurned` is emitted through `emit_cpi!` and the call
+    ///   takes the extra `event_authority` and `program` accounts
+    pub fn burn_and_close_token_account(
+        ctx: Context<BurnAndCloseTokenAccount>,
+        force: bool,
+        deadline: i64,
+        strict_empty: bool,
+    ) -> Result<()> {
+        instructions::burn::burn_and_close_token_account(ctx, force, deadline, strict_empty)
     }
-}
 
-// Account context for `initialize`
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    /// payer & future authority must sign to create the state PDA.
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    /// Program-Derived Account that stores state.
-    ///   • `init`       – create it if it doesn't exist
-    ///   • `payer`      – who funds rent
-    ///   • `space`      – bytes to allocate (8-byte Anchor discriminator + our struct)
-    ///   • `seeds`      – derive address from static seed `b"state"`
-    ///   • `bump`       – auto-adds the bump so the derive matches on-chain 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + BurnerState::INIT_SPACE,   // 8‑byte discriminator + struct size
-        seeds = [b"state"],
-        bump
-    )]
-    pub state: Account<'info, BurnerState>,   
-     
-    /// System program (required by `init` to create accounts)
-    pub system_program: Program<'info, System>,
-}
+    /// Disposes of a token account by sending its whole balance to the incinerator instead of burning.
+    /// * Useful for tokens the user can transfer but not burn
+    /// * Creates the incinerator's ATA for the mint if needed, paid by the user
+    /// * Closes the emptied account, rent goes to the user's vault
+    /// * The amount is booked against the daily burn limit like a burn
+    pub fn dispose_to_incinerator(ctx: Context<DisposeToIncinerator>) -> Result<()> {
+        instructions::burn::dispose_to_incinerator(ctx)
+    }
 
-// Account context for `create_vault`
-#[derive(Accounts)]
-pub struct CreateVault<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>, // wallet creating the vault
-
-    #[account(
-        init,
-        payer = user,
-        space = 8 + VaultAccount::INIT_SPACE,
-        seeds = [b"vault", user.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, VaultAccount>, // vault PDA derived from ("vault", user)
-
-    pub system_program: Program<'info, System>,
-}
+    /// Burns and closes a token account owned by one of this program's PDAs (e.g. an escrow).
+    /// * `seeds` – caller seeds; the owner is derived from `[b"escrow", user, ..seeds, bump]`
+    /// * The derived PDA must equal `token_account.owner`, then signs both CPIs
+    /// * Rent goes to the signing user's vault
+    pub fn burn_and_close_pda_owned(
+        ctx: Context<BurnAndClosePdaOwned>,
+        seeds: Vec<Vec<u8>>,
+        bump: u8,
+    ) -> Result<()> {
+        instructions::burn::burn_and_close_pda_owned(ctx, seeds, bump)
+    }
 
-// Account context for `withdraw_vault`
-#[derive(Accounts)]
-pub struct WithdrawVault<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"vault", user.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
-    )]
-    pub vault: Account<'info, VaultAccount>, // caller's vault PDA, must match owner
-}
+    /// Issuer-controlled retirement: the Token-2022 mint's permanent delegate burns a
+    /// holder's entire balance without the holder signing.
+    /// * Fails with `NotPermanentDelegate` unless the mint's extension names the signer
+    /// * Also closes the account (rent to the holder) if the delegate is its close authority
+    pub fn permanent_delegate_burn(ctx: Context<PermanentDelegateBurn>) -> Result<()> {
+        instructions::burn::permanent_delegate_burn(ctx)
+    }
 
-// Account context for `validate_token_account`
-#[derive(Accounts)]
-pub struct ValidateTokenAccount<'info> {
-    /// User who owns the token account
-    pub user: Signer<'info>,
-    
-    /// SPL Token account to validate
-    /// Anchor's Account<TokenAccount> automatically:
-    /// • Verifies it's owned by the Token Program
-    /// • Deserializes the account data
-    /// • Makes fields like mint, owner, amount available
-    pub token_account: Account<'info, TokenAccount>,
-}
+    /// Burns up to the allowance an owner gave the program's `["burn_delegate", owner]`
+    /// PDA with SPL `approve`, signed by the PDA, so a buyback-and-burn bot can burn
+    /// on a schedule without the owner signing each time.
+    /// * Anyone may submit it; the approved `delegated_amount` is the hard cap, and the
+    ///   owner can `revoke` at any time
+    /// * Fails with `DelegationExceeded` above the remaining allowance
+    pub fn burn_delegated_capped(ctx: Context<BurnDelegatedCapped>, amount: u64) -> Result<()> {
+        instructions::burn::burn_delegated_capped(ctx, amount)
+    }
 
-// Account context for `close_token_account`
-// Designed to work efficiently with ALTs for batch operations
-#[derive(Accounts)]
-pub struct CloseTokenAccount<'info> {
-    /// User who owns the token account
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    /// SPL Token account to close (must be empty)
-    /// Using AccountInfo instead of Account<TokenAccount> would be more ALT-friendly
-    /// but Account<TokenAccount> provides better type safety for now
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    
-    /// User's vault PDA to receive the rent lamports
-    #[account(
-        mut,
-        seeds = [b"vault", user.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
-    )]
-    pub vault: Account<'info, VaultAccount>,
-    
-    /// SPL Token program
-    pub token_program: Program<'info, Token>,
-}
+    /// Burns and closes a token account like `burn_and_close_token_account` and mints one
+    /// token of the state-controlled `receipt_mint` to the user as a burn receipt.
+    /// * Creates the user's receipt ATA if it doesn't exist yet (paid by the user), so the
+    ///   first receipt needs no setup transaction; an existing ATA is reused
+    /// * Fails with `InvalidMintAuthority` once `freeze_receipt_mint` has run
+    /// * No `force`: the supply guardrail and strict mode always apply
+    pub fn burn_with_receipt_init(ctx: Context<BurnWithReceiptInit>) -> Result<()> {
+        instructions::burn::burn_with_receipt_init(ctx)
+    }
 
-// Account context for `burn_and_close_token_account`
-// Designed to work efficiently with ALTs for batch operations
-#[derive(Accounts)]
-pub struct BurnAndCloseTokenAccount<'info> {
-    /// User who owns the token account
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    /// SPL Token account to burn and close
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    
-    /// The mint of the token (required for burning)
-    #[account(mut)]
-    pub mint: Account<'info, anchor_spl::token::Mint>,
-    
-    /// User's vault PDA to receive the rent lamports
-    #[account(
-        mut,
-        seeds = [b"vault", user.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.owner == user.key() @ BurnerError::InvalidOwner
-    )]
-    pub vault: Account<'info, VaultAccount>,
-    
-    /// SPL Token program
-    pub token_program: Program<'info, Token>,
-}
+    /// Resets an ATA: burns its balance, closes it and recreates it empty at the same
+    /// address, in one instruction. Clears anything stuck on the old account, such as a
+    /// leftover delegate approval.
+    /// * The old rent goes back to the user's wallet, which pays for the new account, so
+    ///   the user's net cost is the transaction fee plus any difference in account size
+    /// * The vault is not involved; the burn counts against the daily limit as usual
+    pub fn recycle_token_account(ctx: Context<RecycleTokenAccount>) -> Result<()> {
+        instructions::burn::recycle_token_account(ctx)
+    }
 
-// Persistent data layout – one instance lives at the `state` PDA
-#[account]
-#[derive(InitSpace)]
-pub struct BurnerState {
-    pub authority: Pubkey,   // who can administer the contract
-    pub is_initialized: bool,
-    pub created_at: i64,     // Unix timestamp
-}
+    /// Burns and closes a token account of a token program fork, so long as the fork
+    /// keeps SPL Token's account layout and instructions. `token_program` must be on
+    /// the allowlist managed with `add_token_program`; SPL Token and Token-2022
+    /// accounts have the typed instructions and aren't allowed unless listed too.
+    /// * Fails with `TokenProgramNotAllowed` for any other program and
+    ///   `NotATokenAccount` if the account isn't owned by it or doesn't parse
+    /// * Rent goes to the user's vault; the burn counts against the daily limit
+    pub fn burn_close_any_token_program(ctx: Context<BurnCloseAnyTokenProgram>) -> Result<()> {
+        instructions::burn::burn_close_any_token_program(ctx)
+    }
 
-// Per‑user vault PDA – mainly holds lamports, plus metadata
-#[account]
-#[derive(InitSpace)]
-pub struct VaultAccount {
-    pub owner: Pubkey,           // user controlling withdrawals
-    pub bump: u8,                // PDA bump
-    pub lamports_collected: u64, // optional stats
-}
+    /// Commits `amount` tokens to be burned at `unlock_at`: moves them from `source` into
+    /// an escrow token account owned by a new `ScheduledBurn` PDA and emits `BurnScheduled`.
+    /// * `schedule_id` – any depositor-chosen number, part of the PDA seeds so one mint
+    ///   can have several schedules
+    /// * Requires the depositor's vault, which gets the rent back once the schedule closes
+    /// * Fails with `InvalidUnlockTime` unless `unlock_at` is in the future
+    pub fn schedule_burn(ctx: Context<ScheduleBurn>, schedule_id: u64, amount: u64, unlock_at: i64) -> Result<()> {
+        instructions::schedule::schedule_burn(ctx, schedule_id, amount, unlock_at)
+    }
+
+    /// Burns a scheduled escrow's tokens and closes the escrow and the schedule into the
+    /// depositor's vault. Anyone can call it from `unlock_at` on; before that it fails
+    /// with `BurnNotUnlocked`. The burn counts against the daily limit like any other.
+    pub fn execute_scheduled_burn(ctx: Context<ExecuteScheduledBurn>) -> Result<()> {
+        instructions::schedule::execute_scheduled_burn(ctx)
+    }
+
+    /// Takes a scheduled burn back before it unlocks: the escrowed tokens return to the
+    /// depositor's `destination` account, and the escrow and the schedule close into the
+    /// depositor's vault. Only the depositor can cancel, and only while `now < unlock_at`;
+    /// from then on the burn is committed and this fails with `BurnCommitted`.
+    pub fn cancel_scheduled_burn(ctx: Context<CancelScheduledBurn>) -> Result<()> {
+        instructions::schedule::cancel_scheduled_burn(ctx)
+    }
+
+    /// Support-assisted recovery for an owner who lost their key: proposes `new_owner`
+    /// for any vault. It can be finalized `VAULT_RECOVERY_TIMELOCK_SECS` (7 days) later
+    /// unless the current owner vetoes first; a new proposal replaces a pending one and
+    /// restarts the timelock. Program authority or `ROLE_OPS` only.
+    pub fn propose_vault_recovery(ctx: Context<ProposeVaultRecovery>, new_owner: Pubkey) -> Result<()> {
+        instructions::recovery::propose_vault_recovery(ctx, new_owner)
+    }
+
+    /// Cancels the pending recovery of the caller's vault. Works any time before it is
+    /// finalized, including after the timelock; `NoRecoveryPending` if there is none.
+    pub fn veto_recovery(ctx: Context<VetoRecovery>) -> Result<()> {
+        instructions::recovery::veto_recovery(ctx)
+    }
 
-#[error_code]
-pub enum BurnerError {
-    #[msg("Invalid owner")] // thrown when caller != vault.owner
-    InvalidOwner,
-    
-    #[msg("Token account not owned by user")] // thrown when token account owner != signer
-    UnauthorizedAccount,
-    
-    #[msg("Token account is not empty")] // thrown when trying to close non-empty account
-    AccountNotEmpty,
-}
\ No newline at end of file
+    /// Completes a recovery once its timelock has passed, signed by the proposed owner.
+    /// Vault addresses derive from the owner, so the recovered vault closes into the new
+    /// owner's vault (create it first) and its tallies carry over.
+    /// Stake accounts opened by `compound_vault` keep the old owner as withdrawer.
+    /// * `RecoveryTimelockActive` before `recovery_unlock_at`
+    /// * `NoRecoveryPending` if the signer isn't the proposed owner
+    pub fn finalize_vault_recovery(ctx: Context<FinalizeVaultRecovery>) -> Result<()> {
+        instructions::recovery::finalize_vault_recovery(ctx)
+    }
+
+    /// Issuer-controlled retirement for SPL Token or Token-2022: the mint's freeze authority
+    /// thaws (if needed), burns and closes a holder's account.
+    /// * Fails with `NotFreezeAuthority` unless the signer is the mint's freeze authority
+    /// * The holder must have made the issuer the close authority, and the issuer needs burn
+    ///   rights: an approved delegate for the balance, or the Token-2022 permanent delegate
+    /// * Rent always goes to the holder's vault, never to the issuer
+    pub fn issuer_retire_account(ctx: Context<IssuerRetireAccount>) -> Result<()> {
+        instructions::burn::issuer_retire_account(ctx)
+    }
+
+    /// Burns and closes like `burn_and_close_token_account`, then splits the reclaimed
+    /// rent three ways: `referral_bps` to the referrer's vault, `treasury_bps` to the
+    /// treasury, and the exact remainder to the user's vault. The user's vault never
+    /// drops under its rent floor: a shortfall is taken back from the treasury share
+    /// first, then the referral share. `mint` must be the token account's mint, and
+    /// `mint_fee` its `MintFeeOverride` address: a set override always applies, an
+    /// uninitialized one falls back to `treasury_bps`.
+    pub fn burn_close_refer(ctx: Context<BurnCloseRefer>) -> Result<()> {
+        instructions::refer::burn_close_refer(ctx)
+    }
+
+    /// Relayer-submitted burn + close, made retry-safe by `idempotency_key`.
+    /// * The first successful call records the key in an `IdempotencyRecord` PDA
+    /// * Any later call with the same key returns success without re-burning
+    /// * The relayer (`payer`) funds the record; the user still signs as token authority
+    /// * `operator_fee_lamports` – optional cut of the reclaimed rent for the relayer;
+    ///   requires `payer` to be the vault's approved operator and can't exceed the rent
+    /// * Creates the user's vault if needed; the relayer fronts its rent and is paid
+    ///   back from the reclaimed rent, capped at the creation cost
+    pub fn relayed_burn_and_close(
+        ctx: Context<RelayedBurnAndClose>,
+        idempotency_key: [u8; 32],
+        operator_fee_lamports: Option<u64>,
+    ) -> Result<()> {
+        instructions::relay::relayed_burn_and_close(ctx, idempotency_key, operator_fee_lamports)
+    }
+
+    /// Closes an old `IdempotencyRecord` and refunds its rent to the relayer that paid for it.
+    /// Only allowed once `IDEMPOTENCY_RETENTION_SECS` have passed, after which a
+    /// resubmission of the original transaction can no longer land anyway.
+    pub fn close_idempotency_record(ctx: Context<CloseIdempotencyRecord>) -> Result<()> {
+        instructions::relay::close_idempotency_record(ctx)
+    }
+
+    /// Burns a fraction of the token account's current balance, expressed in basis points.
+    /// * `bps` – 1..=10000, where 10000 burns the full balance; 0 fails with `InvalidBps`
+    /// * Rounds down; the account stays open
+    /// * A zero balance, or a share that rounds down to 0, is a logged no-op rather than
+    ///   an error. The burn-and-close variants likewise skip the burn for an empty account
+    ///   and just close it
+    pub fn burn_bps(ctx: Context<BurnBps>, bps: u16) -> Result<()> {
+        instructions::burn::burn_bps(ctx, bps)
+    }
+
+    /// Burns just enough from the token account to bring the mint's supply down to
+    /// `target_supply`, at most the account's whole balance; the account stays open.
+    /// Returns a `SupplyBurnOutcome` whose `shortfall` says how far above the target
+    /// the supply still is, for campaigns that finish the job over more transactions
+    /// or accounts. A supply already at or below the target burns nothing.
+    pub fn burn_to_supply(ctx: Context<BurnToSupply>, target_supply: u64) -> Result<SupplyBurnOutcome> {
+        instructions::burn::burn_to_supply(ctx, target_supply)
+    }
+
+    /// Burns and closes many token accounts in one instruction.
+    /// * `remaining_accounts` – flat list of `(token_account, mint, mint_burn_limit)` triples,
+    ///   all writable; `mint_burn_limit` is the mint's `MintBurnLimit` PDA, initialized or not,
+    ///   and the burn counts against its daily cap as on the single-account paths
+    ///   Legacy and Token-2022 accounts can be mixed; each entry's CPIs go to the program
+    ///   that owns it, and Token-2022 entries need the optional `token_2022_program`
+    /// * Rent is booked per entry from the vault's actual lamport gain, so accounts of
+    ///   different sizes each count what they really held
+    /// * `allow_partial = false` – any pair failing validation aborts the whole batch
+    /// * `allow_partial = true`  – failing pairs are logged and skipped
+    /// * A token account paired with a mint it doesn't belong to is skipped in both
+    ///   modes, with `BATCH_STATUS_PAIR_MISMATCH`
+    /// * `rescue_owner` set – rescue mode: entries become `(token_account, mint, mint_burn_limit,
+    ///   rescue_account)`, and accounts the program won't burn (a daily limit reached) have their
+    ///   tokens transferred to `rescue_account` before closing instead of aborting
+    /// * Optional `epoch_stats` – booked with the batch totals, as in `burn_and_close_token_account`
+    /// * More than `MAX_BATCH_ACCOUNTS` (48) remaining accounts fail up front with `BatchTooLarge`
+    /// * `start_index` – first remaining account to process, on an entry boundary; 0 for
+    ///   a fresh batch. Entries before it are ignored, so an interrupted batch is resumed
+    ///   by resubmitting the same list from the returned `next_index`
+    /// * Stops before an entry once the remaining compute can't cover it plus the wrap-up,
+    ///   committing what was done; `next_index` then points at that entry. A resume that
+    ///   makes no progress needs a higher compute-unit limit
+    /// Returns a `BatchOutcome`: one `BatchResult` per processed entry (burned, rescued,
+    /// pair mismatch, or the skip error code) and `next_index`, the list's length once
+    /// every entry is done. Batches above `MAX_BATCH_RESULTS` entries return no results
+    /// and, at event level 2, emit a `BatchAccountResult` event per entry instead. Only
+    /// pre-CPI validation can be skipped; a failing token CPI always aborts the transaction.
+    pub fn batch_burn_and_close<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchBurnAndClose<'info>>,
+        allow_partial: bool,
+        start_index: u16,
+    ) -> Result<BatchOutcome> {
+        instructions::batch::batch_burn_and_close(ctx, allow_partial, start_index)
+    }
+
+    /// Previews what cleaning up the remaining accounts would net `user`, as a
+    /// `BatchNetPreview` in return data:
+    /// * Gross: the lamports of every account `classify_batch` would call burnable or empty
+    /// * Minus the protocol fee: the configured referral + treasury rent split
+    /// * Minus `ESTIMATED_TX_FEE_LAMPORTS` per `batch_burn_and_close` transaction needed
+    ///   (16 entries each); priority fees aren't included
+    /// Up to `MAX_BATCH_ACCOUNTS` accounts per call.
+    pub fn preview_batch_net(ctx: Context<PreviewBatchNet>) -> Result<BatchNetPreview> {
+        instructions::batch::preview_batch_net(ctx)
+    }
+
+    /// One-signature cleanup: closes every remaining account (empty SPL Token or
+    /// Token-2022 accounts of the signer, no mints) into the vault, then withdraws the
+    /// vault's usable lamports to the signer like `withdraw_vault`. Any failing account
+    /// fails the whole call, e.g. `AccountNotEmpty` for one with a balance. Cosigner,
+    /// `min_withdraw_lamports` and the withdraw cooldown apply as for `withdraw_vault`.
+    /// Up to `MAX_BATCH_ACCOUNTS` accounts per call.
+    pub fn cleanup_all<'info>(ctx: Context<'_, '_, 'info, 'info, CleanupAll<'info>>) -> Result<()> {
+        instructions::batch::cleanup_all(ctx)
+    }
+
+    /// Counts the signer's closable accounts, passed as remaining_accounts, into their
+    /// `ReclaimCache` PDA (created on first use). UIs read the cache for an instant
+    /// total instead of simulating `preview_batch_net`, and refresh it once
+    /// `refreshed_at` is older than they're willing to show.
+    pub fn refresh_reclaim_cache(ctx: Context<RefreshReclaimCache>) -> Result<()> {
+        instructions::batch::refresh_reclaim_cache(ctx)
+    }
+
+    /// Returns the compute-unit limit clients should request for a batch of `n` pairs.
+    /// The program can't raise its own budget, so clients prepend
+    /// `ComputeBudgetProgram::setComputeUnitLimit` with this value.
+    pub fn preview_cu_for_batch(ctx: Context<PreviewCuForBatch>, n: u32) -> Result<u32> {
+        instructions::batch::preview_cu_for_batch(ctx, n)
+    }
+
+    /// Permanently caps the receipt mint by revoking its mint authority.
+    /// The state PDA is the receipt mint authority, so it signs the
+    /// SetAuthority CPI with its seeds. There is deliberately no way back.
+    pub fn freeze_receipt_mint(ctx: Context<FreezeReceiptMint>) -> Result<()> {
+        instructions::admin::freeze_receipt_mint(ctx)
+    }
+
+    /// Sets the fewest token accounts `batch_burn_and_close` accepts, failing smaller
+    /// batches with `BatchTooSmall` so fixed per-call costs get amortized. Counts
+    /// entries, not remaining accounts; at most `MAX_BATCH_ACCOUNTS / 3`. 0 (the
+    /// default) accepts any size. Single accounts still go through the non-batch
+    /// instructions. Admin or `ROLE_LIMITS`.
+    pub fn set_min_batch_count(ctx: Context<SetMinBatchCount>, count: u8) -> Result<()> {
+        instructions::admin::set_min_batch_count(ctx, count)
+    }
+
+    /// Sets the balance up to which `close_token_account` burns the dust and closes
+    /// anyway (0 = only truly empty accounts, the default). Admin or `ROLE_LIMITS`.
+    pub fn set_dust_threshold(ctx: Context<SetDustThreshold>, threshold: u64) -> Result<()> {
+        instructions::admin::set_dust_threshold(ctx, threshold)
+    }
+
+    /// Sets the smallest amount `withdraw_vault` will move (0 = any, the default),
+    /// so users batch their reclaim. Admin or `ROLE_LIMITS`.
+    pub fn set_min_withdraw(ctx: Context<SetMinWithdraw>, lamports: u64) -> Result<()> {
+        instructions::admin::set_min_withdraw(ctx, lamports)
+    }
+
+    /// Sets the minimum number of seconds between two withdrawals from the same vault
+    /// (0 = none, the default). Admin or `ROLE_LIMITS`. Burns are never gated by it,
+    /// so burning and then withdrawing straight away still works.
+    pub fn set_withdraw_cooldown(ctx: Context<SetWithdrawCooldown>, secs: u32) -> Result<()> {
+        instructions::admin::set_withdraw_cooldown(ctx, secs)
+    }
+
+    /// Arms the circuit breaker: once more than `threshold` raw tokens are burned
+    /// within one `ANOMALY_WINDOW_SECS` (5 min) window, burns pause automatically.
+    /// The burn that crosses the threshold still goes through; the rest get
+    /// `ProgramPaused` until `set_paused(false)`. 0 disarms it. Starts a fresh window.
+    /// Admin or `ROLE_LIMITS`.
+    pub fn set_anomaly_threshold(ctx: Context<SetAnomalyThreshold>, threshold: u64) -> Result<()> {
+        instructions::admin::set_anomaly_threshold(ctx, threshold)
+    }
+
+    /// Sets the global cap on raw tokens burned per 24h window (0 = unlimited).
+    /// Admin or `ROLE_LIMITS`.
+    pub fn set_daily_burn_limit(ctx: Context<SetDailyBurnLimit>, limit: u64) -> Result<()> {
+        instructions::admin::set_daily_burn_limit(ctx, limit)
+    }
+
+    /// Caps a single non-forced `burn_and_close_token_account` at `bps` of the
+    /// mint's current supply (0 = disabled, the default). Admin or `ROLE_LIMITS`.
+    pub fn set_max_supply_pct_bps(ctx: Context<SetMaxSupplyPctBps>, bps: u16) -> Result<()> {
+        instructions::admin::set_max_supply_pct_bps(ctx, bps)
+    }
+
+    /// Sets which burn/batch events are emitted: 0 = none, 1 = one rollup per
+    /// instruction, 2 = rollup plus per-account events (the default). Admin or `ROLE_OPS`.
+    pub fn set_event_level(ctx: Context<SetEventLevel>, level: u8) -> Result<()> {
+        instructions::admin::set_event_level(ctx, level)
+    }
+
+    /// Pins the program's notion of "now" to `unix_timestamp` and the current epoch
+    /// to `epoch` (0 restores the cluster clock for either). Admin-only, and only
+    /// built with the `test-helpers` feature.
+    #[cfg(feature = "test-helpers")]
+    pub fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: i64, epoch: u64) -> Result<()> {
+        instructions::admin::set_mock_clock(ctx, unix_timestamp, epoch)
+    }
+
+    /// Forces the `processing` reentrancy flag, simulating a burn still in flight.
+    /// Admin-only, and only built with the `test-helpers` feature.
+    #[cfg(feature = "test-helpers")]
+    pub fn set_processing_flag(ctx: Context<SetProcessingFlag>, processing: bool) -> Result<()> {
+        instructions::admin::set_processing_flag(ctx, processing)
+    }
+
+    /// Overwrites a vault's `bump`, `owner` and `rent_floor_snapshot` with bad values, so
+    /// tests can exercise `repair_vault`. Admin-only, and only built with the
+    /// `test-helpers` feature.
+    #[cfg(feature = "test-helpers")]
+    pub fn corrupt_vault(ctx: Context<CorruptVault>, bump: u8, owner: Pubkey) -> Result<()> {
+        instructions::admin::corrupt_vault(ctx, bump, owner)
+    }
+
+    /// Shrinks the state account to `len` bytes, e.g. back to a layout from before
+    /// `state_bump`, so tests can exercise `migrate_state_bump`. Admin-only, and only
+    /// built with the `test-helpers` feature.
+    #[cfg(feature = "test-helpers")]
+    pub fn truncate_state(ctx: Context<TruncateState>, len: u32) -> Result<()> {
+        instructions::admin::truncate_state(ctx, len)
+    }
+
+    /// Shrinks any vault to `len` bytes, e.g. back to the original three-field layout,
+    /// so tests can exercise `migrate_vault`. Admin-only, and only built with the
+    /// `test-helpers` feature.
+    #[cfg(feature = "test-helpers")]
+    pub fn truncate_vault(ctx: Context<TruncateVault>, len: u32) -> Result<()> {
+        instructions::admin::truncate_vault(ctx, len)
+    }
+
+    /// Moves the treasury: everything the old treasury holds above its rent floor goes
+    /// to `new_treasury`, which becomes `state.treasury`. Admin or `ROLE_FEES`.
+    /// * The treasury is a plain wallet rather than a program PDA, so it has to co-sign
+    /// * `close_old` – move the whole balance, floor included, leaving the old account deleted
+    /// * Emits `TreasuryMigrated`
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>, close_old: bool) -> Result<()> {
+        instructions::admin::migrate_treasury(ctx, close_old)
+    }
+
+    /// Caps how many raw tokens of `mint` can be burned per rolling 24h window on
+    /// every burn path, batch entries included; burns past it fail with
+    /// `MintDailyCapExceeded`. 0 lifts the cap. Admin or `ROLE_LIMITS`.
+    pub fn set_mint_daily_cap(ctx: Context<SetMintDailyCap>, daily_cap: u64) -> Result<()> {
+        instructions::admin::set_mint_daily_cap(ctx, daily_cap)
+    }
+
+    /// Sets a per-mint treasury fee that `burn_close_refer` uses instead of the global
+    /// `treasury_bps` when the override is passed. Admin or `ROLE_FEES`.
+    /// `referral_bps + fee_bps` must be at most 10000.
+    pub fn set_mint_fee(ctx: Context<SetMintFee>, fee_bps: u16) -> Result<()> {
+        instructions::admin::set_mint_fee(ctx, fee_bps)
+    }
+
+    /// Runs a fee-free promotion until the Unix timestamp `until`: while `now < until`
+    /// the rent split gives the user everything and the token fee isn't charged, whatever
+    /// their configuration. Ends on its own; 0 (or any past time) ends it early.
+    /// Admin or `ROLE_FEES`.
+    pub fn set_fee_waiver(ctx: Context<SetFeeWaiver>, until: i64) -> Result<()> {
+        instructions::admin::set_fee_waiver(ctx, until)
+    }
+
+    /// Charges a fixed fee of `amount` raw `fee_mint` tokens on every
+    /// `burn_and_close_token_account`, paid into `fee_treasury` before the burn.
+    /// A separate model from the rent split; 0 turns it off. Admin or `ROLE_FEES`.
+    /// * Only `burn_and_close_token_account` charges it. Every other burn path
+    ///   (batches, `burn_close_refer`, `burn_with_receipt_init`, `recycle_token_account`
+    ///   and the rest) burns without it, so it is a fee on that one instruction and
+    ///   can't be relied on as a charge on every burn
+    /// * `fee_token_program` must be the program owning `fee_mint`
+    pub fn set_token_fee(ctx: Context<SetTokenFee>, amount: u64) -> Result<()> {
+        instructions::admin::set_token_fee(ctx, amount)
+    }
+
+    /// Allows `program` as a target of `burn_close_any_token_program`, in one of
+    /// `MAX_ALLOWED_TOKEN_PROGRAMS` slots. Main authority only; a no-op when already listed.
+    pub fn add_token_program(ctx: Context<SetTokenProgramAllowlist>, program: Pubkey) -> Result<()> {
+        instructions::admin::add_token_program(ctx, program)
+    }
+
+    /// Takes `program` off the token program allowlist. Main authority only.
+    pub fn remove_token_program(ctx: Context<SetTokenProgramAllowlist>, program: Pubkey) -> Result<()> {
+        instructions::admin::remove_token_program(ctx, program)
+    }
+
+    /// Removes a mint's fee override, refunding its rent to the signer. Admin or `ROLE_FEES`.
+    pub fn clear_mint_fee(ctx: Context<ClearMintFee>) -> Result<()> {
+        instructions::admin::clear_mint_fee(ctx)
+    }
+
+    /// Configures the `burn_close_refer` rent split. Admin or `ROLE_FEES`.
+    /// `referral_bps + treasury_bps` must be at most 10000; the user keeps the rest.
+    pub fn set_rent_split(
+        ctx: Context<SetRentSplit>,
+        referral_bps: u16,
+        treasury_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::set_rent_split(ctx, referral_bps, treasury_bps, treasury)
+    }
+
+    /// Hands the program to a DAO in one step: `authority` and `treasury` both become
+    /// `dao_authority`, a PDA of `dao_program` re-derived from `dao_seeds` (bump
+    /// included), and every delegated role is cleared. From then on admin actions need
+    /// the DAO's signature. `confirm` must be true. Main-authority-only.
+    /// * `InvalidDaoAuthority` if the seeds don't derive `dao_authority`
+    pub fn handover_to_dao(ctx: Context<HandoverToDao>, dao_seeds: Vec<Vec<u8>>, confirm: bool) -> Result<()> {
+        instructions::admin::handover_to_dao(ctx, dao_seeds, confirm)
+    }
+
+    /// Delegates admin role `role` (a `ROLE_*`) to `holder`, or clears it with `None`.
+    /// Role holders can run that role's admin actions alongside the main authority,
+    /// which stays the only key that can call this. Main-authority-only.
+    pub fn set_role(ctx: Context<SetRole>, role: u8, holder: Option<Pubkey>) -> Result<()> {
+        instructions::admin::set_role(ctx, role, holder)
+    }
+
+    /// Enables (or disables) `reset_stats`. Off by default; main-authority-only.
+    /// Fails with `StatsResetLocked` once `lock_stats_reset` has run.
+    pub fn set_stats_resettable(ctx: Context<SetStatsResettable>, resettable: bool) -> Result<()> {
+        instructions::admin::set_stats_resettable(ctx, resettable)
+    }
+
+    /// Turns `reset_stats` off for good, for production deployments. Irreversible;
+    /// main-authority-only.
+    pub fn lock_stats_reset(ctx: Context<SetStatsResettable>) -> Result<()> {
+        instructions::admin::lock_stats_reset(ctx)
+    }
+
+    /// Zeroes the lifetime counters on `BurnerState` (`total_burned`, `accounts_closed`,
+    /// the `closed_vault_*` totals, `last_folded_at`), and the optional `vault`'s
+    /// `lamports_collected`, `tokens_burned` and `accounts_closed`. Main-authority-only,
+    /// and only while `stats_resettable`.
+    /// * The daily burn window is left alone, it's a limit rather than a statistic
+    /// * Pending shard totals aren't touched; fold them first for a clean zero
+    /// * Emits `StatsReset`
+    pub fn reset_stats(ctx: Context<ResetStats>) -> Result<()> {
+        instructions::admin::reset_stats(ctx)
+    }
+
+    /// Creates shard counter `index` (< `NUM_SHARDS`). Admin-only, once per index.
+    pub fn init_shard(ctx: Context<InitShard>, index: u8) -> Result<()> {
+        instructions::shard::init_shard(ctx, index)
+    }
+
+    /// Drains the shard counters passed as remaining accounts into the lifetime
+    /// totals on `BurnerState`. Admin-only; run periodically.
+    /// Consistency: state totals lag until the next fold, and shards omitted from
+    /// a fold simply carry their pending amounts over. The daily burn limit is not
+    /// sharded – it needs one exact global figure, so burns still write-lock `state`.
+    /// Takes at most `MAX_BATCH_ACCOUNTS` shards per call.
+    pub fn fold_shards<'info>(ctx: Context<'_, '_, 'info, 'info, FoldShards<'info>>) -> Result<()> {
+        instructions::shard::fold_shards(ctx)
+    }
+
+    /// One-off migration for state PDAs created before `state_bump` existed: derives the
+    /// canonical bump once and stores it. Main authority only.
+    /// * An account on an older, shorter layout is grown to the current size first, the
+    ///   authority paying the extra rent; fields it didn't have yet start zeroed (off)
+    /// * Sets `version` to `STATE_VERSION`
+    /// * Every other context checks `state` against the stored bump, so they all fail
+    ///   with a seeds error until this has run
+    pub fn migrate_state_bump(ctx: Context<MigrateStateBump>) -> Result<()> {
+        instructions::admin::migrate_state_bump(ctx)
+    }
+
+    /// Support tool: closes any user's empty token account into that user's own vault.
+    /// Admin or `ROLE_OPS`.
+    /// * The owner must have set the state PDA as the account's close authority first
+    /// * The vault is derived from and checked against `token_account.owner`, so the
+    ///   admin can never route the rent anywhere else
+    pub fn admin_close_empty(ctx: Context<AdminCloseEmpty>) -> Result<()> {
+        instructions::admin::admin_close_empty(ctx)
+    }
+
+    /// Overwrites a vault's stats with authoritative historical values computed
+    /// off-chain after a data migration (`lamports_collected`, `tokens_burned`,
+    /// `accounts_closed`). Admin or `ROLE_OPS`, at most once per vault.
+    /// * A vault still on an older layout must go through `migrate_vault` first
+    pub fn backfill_vault_stats(
+        ctx: Context<BackfillVaultStats>,
+        lamports_collected: u64,
+        tokens_burned: u128,
+        accounts_closed: u64,
+    ) -> Result<()> {
+        instructions::admin::backfill_vault_stats(ctx, lamports_collected, tokens_burned, accounts_closed)
+    }
+}
@@ -0,0 +1,92 @@
+//! Per-account log lines for the close, burn and batch hot paths.
+//!
+//! By default each helper writes the usual human-readable `msg!`. With the
+//! `compact-logs` feature it instead writes one `sol_log_data` record, which
+//! shows up as `Program data: <b64> <b64> ...` with one base64 chunk per field.
+//! The first chunk is always a single `LOG_TAG_*` byte; integers are
+//! little-endian and pubkeys are the raw 32 bytes:
+//!
+//! | tag                        | fields after the tag                             |
+//! |----------------------------|--------------------------------------------------|
+//! | `LOG_TAG_CLOSED`           | mint, owner, rent `u64`                          |
+//! | `LOG_TAG_BURNED_CLOSED`    | mint, owner, amount `u64`, rent `u64`            |
+//! | `LOG_TAG_BATCH_SKIPPED`    | entry index `u32`, token account, error `u32`    |
+//! | `LOG_TAG_BATCH_RESCUED`    | entry index `u32`, token account, error `u32`    |
+//! | `LOG_TAG_BATCH_SUMMARY`    | processed `u32`, entries `u32`, rent `u64`       |
+//!
+//! Anchor events are also `Program data:` lines; they start with an 8-byte
+//! discriminator, so a one-byte first chunk is always one of these records.
+
+use anchor_lang::prelude::*;
+#[cfg(feature = "compact-logs")]
+use anchor_lang::solana_program::log::sol_log_data;
+
+#[cfg(feature = "compact-logs")]
+use crate::constants::*;
+
+/// An empty token account was closed into `owner`'s vault.
+#[cfg_attr(not(feature = "compact-logs"), allow(unused_variables))]
+pub fn account_closed(mint: &Pubkey, owner: &Pubkey, rent: u64) {
+    #[cfg(feature = "compact-logs")]
+    sol_log_data(&[&[LOG_TAG_CLOSED], mint.as_ref(), owner.as_ref(), &rent.to_le_bytes()]);
+    #[cfg(not(feature = "compact-logs"))]
+    msg!("Token account closed successfully, {} lamports sent to vault", rent);
+}
+
+/// `amount` tokens were burned and the account closed into `owner`'s vault.
+#[cfg_attr(not(feature = "compact-logs"), allow(unused_variables))]
+pub fn burned_and_closed(mint: &Pubkey, owner: &Pubkey, amount: u64, rent: u64) {
+    #[cfg(feature = "compact-logs")]
+    sol_log_data(&[
+        &[LOG_TAG_BURNED_CLOSED],
+        mint.as_ref(),
+        owner.as_ref(),
+        &amount.to_le_bytes(),
+        &rent.to_le_bytes(),
+    ]);
+    #[cfg(not(feature = "compact-logs"))]
+    msg!(
+        "Burned {} tokens and closed account successfully, {} lamports sent to vault",
+        amount,
+        rent
+    );
+}
+
+/// Batch entry `index` was skipped, `err` being why.
+pub fn batch_skipped(index: usize, token_account: &Pubkey, err: &Error) {
+    #[cfg(feature = "compact-logs")]
+    sol_log_data(&[
+        &[LOG_TAG_BATCH_SKIPPED],
+        &(index as u32).to_le_bytes(),
+        token_account.as_ref(),
+        &crate::instructions::batch::error_code(err).to_le_bytes(),
+    ]);
+    #[cfg(not(feature = "compact-logs"))]
+    msg!("Skipping pair {} ({}): {:?}", index, token_account, err);
+}
+
+/// Batch entry `index` was rescued instead of burned, `err` being why.
+pub fn batch_rescued(index: usize, token_account: &Pubkey, err: &Error) {
+    #[cfg(feature = "compact-logs")]
+    sol_log_data(&[
+        &[LOG_TAG_BATCH_RESCUED],
+        &(index as u32).to_le_bytes(),
+        token_account.as_ref(),
+        &crate::instructions::batch::error_code(err).to_le_bytes(),
+    ]);
+    #[cfg(not(feature = "compact-logs"))]
+    msg!("Rescuing entry {} ({}) instead of burning: {:?}", index, token_account, err);
+}
+
+/// A batch finished: `processed` of `entries` closed, `rent` sent to the vault.
+pub fn batch_summary(processed: u32, entries: usize, rent: u64) {
+    #[cfg(feature = "compact-logs")]
+    sol_log_data(&[
+        &[LOG_TAG_BATCH_SUMMARY],
+        &processed.to_le_bytes(),
+        &(entries as u32).to_le_bytes(),
+        &rent.to_le_bytes(),
+    ]);
+    #[cfg(not(feature = "compact-logs"))]
+    msg!("Batch processed {} of {} accounts, {} lamports sent to vault", processed, entries, rent);
+}
@@ -0,0 +1,574 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    ANOMALY_WINDOW_SECS, BURN_WINDOW_SECS, MAX_ALLOWED_TOKEN_PROGRAMS, MAX_BPS, MINT_BURN_LIMIT_SEED, NUM_ROLES,
+    RECLAIM_HISTORY_LEN,
+};
+use crate::errors::BurnerError;
+use crate::events::CircuitBreakerTripped;
+
+// Persistent data layout – one instance lives at the `state` PDA
+#[account]
+#[derive(InitSpace)]
+pub struct BurnerState {
+    pub authority: Pubkey,   // who can administer the contract
+    pub is_initialized: bool,
+    pub created_at: i64,     // Unix timestamp
+    pub daily_burn_limit: u64,  // max raw tokens burned per window across all users, 0 = unlimited
+    pub window_start: i64,      // Unix timestamp the current burn window opened
+    pub burned_in_window: u128, // raw tokens burned since window_start
+    pub total_burned: u128,     // lifetime raw tokens burned, as of the last fold_shards
+    pub accounts_closed: u64,   // lifetime closed token accounts, as of the last fold_shards
+    pub last_folded_at: i64,    // Unix timestamp of the last fold_shards, 0 = never
+    pub max_supply_pct_bps: u16, // single-burn cap as bps of mint supply, 0 = disabled
+    pub event_level: u8,         // EVENT_LEVEL_*: which burn/batch events get emitted
+    pub referral_bps: u16,       // referrer's share of rent in burn_close_refer
+    pub treasury_bps: u16,       // treasury's share of rent in burn_close_refer
+    pub treasury: Pubkey,        // receives the treasury share
+    pub mock_unix_timestamp: i64, // set_mock_clock override, 0 = real clock; only read with `test-helpers`
+    pub mock_epoch: u64,         // set_mock_clock epoch override, 0 = real clock; same gating
+    pub roles: [Pubkey; NUM_ROLES], // ROLE_* delegates, Pubkey::default() = unassigned
+    pub closed_vault_lamports: u128, // lamports_collected of every vault closed via batch_close_vaults
+    pub dust_threshold: u64,     // close_token_account burns and closes balances up to this, 0 = strict
+    pub min_withdraw_lamports: u64, // withdraw_vault refuses smaller withdrawals, 0 = any amount
+    pub processing: bool,        // reentrancy guard, true only while a burn instruction runs
+    pub state_bump: u8,          // canonical bump of this PDA, 0 until initialize or migrate_state_bump
+    pub version: u8,             // STATE_VERSION the account was laid out with, 0 = predates versioning
+    pub withdraw_cooldown_secs: u32, // minimum gap between withdrawals from one vault, 0 = none
+    pub vault_creation_paused: bool, // blocks new vaults only; existing ones keep working
+    pub stats_resettable: bool,  // reset_stats allowed; off unless explicitly enabled
+    pub stats_reset_locked: bool, // set by lock_stats_reset, keeps stats_resettable off for good
+    pub token_fee_amount: u64,   // raw fee tokens burn_and_close_token_account charges first, 0 = off
+    pub token_fee_mint: Pubkey,  // mint the token fee is paid in
+    pub token_fee_treasury: Pubkey, // token account of token_fee_mint receiving it
+    pub is_paused: bool,         // every burn refused until set_paused(false); tripped by the breaker too
+    pub anomaly_threshold: u64,  // raw tokens per ANOMALY_WINDOW_SECS that trip the breaker, 0 = off
+    pub anomaly_window_start: i64, // Unix timestamp the current breaker window opened
+    pub burned_in_anomaly_window: u128, // raw tokens burned since anomaly_window_start
+    pub min_batch_count: u8,     // fewest token accounts batch_burn_and_close accepts, 0 = any
+    pub check_duplicate_closes: bool, // reject a close repeated within one transaction; costs CU
+    pub token_program_allowlist: [Pubkey; MAX_ALLOWED_TOKEN_PROGRAMS], // burn_close_any_token_program targets, Pubkey::default() = free slot
+    pub total_rent_reclaimed: u128, // lifetime rent lamports of shard-booked closes, as of the last fold_shards
+    pub last_active_at: i64,     // Unix timestamp of the last burn on any path, 0 = none yet
+    pub fee_waiver_until: i64,   // rent split and token fee waived while now < this, 0 = no waiver
+    pub closed_vault_tokens_burned: u128, // tokens_burned of every vault closed via batch_close_vaults
+    pub closed_vault_accounts_closed: u64, // accounts_closed of those vaults
+}
+
+impl BurnerState {
+    /// Current Unix timestamp for every time-based check. With the
+    /// `test-helpers` feature a non-zero `mock_unix_timestamp` takes the
+    /// place of the cluster clock; production builds always read `Clock`.
+    pub fn now(&self) -> Result<i64> {
+        #[cfg(feature = "test-helpers")]
+        if self.mock_unix_timestamp != 0 {
+            return Ok(self.mock_unix_timestamp);
+        }
+        Ok(Clock::get()?.unix_timestamp)
+    }
+
+    /// Current Solana epoch, keying `EpochStats`. Mockable like `now`.
+    pub fn epoch(&self) -> Result<u64> {
+        #[cfg(feature = "test-helpers")]
+        if self.mock_epoch != 0 {
+            return Ok(self.mock_epoch);
+        }
+        Ok(Clock::get()?.epoch)
+    }
+
+    /// Books `amount` against the rolling daily limit, opening a fresh
+    /// window first if more than `BURN_WINDOW_SECS` passed since the last one.
+    /// Every burn path goes through here, so this is also where a pause is
+    /// enforced and the circuit breaker counts (see `record_anomaly_volume`).
+    /// A zero `amount`, i.e. closing an empty account, passes a pause.
+    pub fn record_burn(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(amount == 0 || !self.is_paused, BurnerError::ProgramPaused);
+
+        if now.saturating_sub(self.window_start) > BURN_WINDOW_SECS {
+            self.window_start     = now;
+            self.burned_in_window = 0;
+        }
+
+        let burned = self
+            .burned_in_window
+            .checked_add(amount as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+        require!(
+            self.daily_burn_limit == 0 || burned <= self.daily_burn_limit as u128,
+            BurnerError::DailyLimitExceeded
+        );
+
+        self.burned_in_window = burned;
+        self.last_active_at   = now;
+        self.record_anomaly_volume(amount, now)
+    }
+
+    /// Circuit breaker: once more than `anomaly_threshold` raw tokens are burned
+    /// inside one `ANOMALY_WINDOW_SECS` window, sets `is_paused`. A failed
+    /// instruction would discard the flag, so the burn that crosses the threshold
+    /// still lands; it's everything after it, later entries of the same batch
+    /// included, that gets `ProgramPaused`. Only the admin's `set_paused(false)`
+    /// resumes, opening a fresh window.
+    fn record_anomaly_volume(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now.saturating_sub(self.anomaly_window_start) > ANOMALY_WINDOW_SECS {
+            self.anomaly_window_start     = now;
+            self.burned_in_anomaly_window = 0;
+        }
+        self.burned_in_anomaly_window = self
+            .burned_in_anomaly_window
+            .checked_add(amount as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+
+        if self.anomaly_threshold > 0 && self.burned_in_anomaly_window > self.anomaly_threshold as u128 {
+            self.is_paused = true;
+            msg!(
+                "Circuit breaker tripped: {} burned since {}, threshold {}",
+                self.burned_in_anomaly_window,
+                self.anomaly_window_start,
+                self.anomaly_threshold
+            );
+            emit!(CircuitBreakerTripped {
+                burned_in_window: self.burned_in_anomaly_window,
+                threshold: self.anomaly_threshold,
+                window_start: self.anomaly_window_start,
+            });
+        }
+        Ok(())
+    }
+
+    /// Starts a fresh, empty circuit-breaker window at `now`.
+    pub fn reset_anomaly_window(&mut self, now: i64) {
+        self.anomaly_window_start     = now;
+        self.burned_in_anomaly_window = 0;
+    }
+
+    /// Reentrancy guard for instructions that CPI into a token program while holding
+    /// `state`. A call re-entering mid-burn (say a token program or transfer hook
+    /// calling back into us) would read the pre-burn daily window from account
+    /// data, book its own burn on top, and then have the outer call's write-back
+    /// erase it. The runtime already refuses A -> B -> A reentrancy and token
+    /// programs are pinned to SPL's, so this is defence in depth.
+    ///
+    /// The flag is written to account data right away, since Anchor only
+    /// serializes accounts when the instruction returns. Only success paths need
+    /// `end_processing`: a failed instruction discards every account write,
+    /// the flag included, so it can't be left stuck.
+    pub fn begin_processing(state: &mut Account<BurnerState>, program_id: &Pubkey) -> Result<()> {
+        require!(!state.processing, BurnerError::ReentrantCall);
+        state.processing = true;
+        state.exit(program_id)
+    }
+
+    /// Clears the `begin_processing` flag; call right before returning `Ok`.
+    pub fn end_processing(&mut self) {
+        self.processing = false;
+    }
+
+    /// Whether `key` may run actions gated on `role` (a `ROLE_*`): the main
+    /// authority always can, plus whoever holds the role.
+    pub fn is_authorized(&self, role: u8, key: &Pubkey) -> bool {
+        *key == self.authority
+            || self
+                .roles
+                .get(role as usize)
+                .is_some_and(|holder| *holder != Pubkey::default() && holder == key)
+    }
+
+    /// Whether `program` is on the `token_program_allowlist`.
+    pub fn allows_token_program(&self, program: &Pubkey) -> bool {
+        *program != Pubkey::default() && self.token_program_allowlist.contains(program)
+    }
+
+    /// Whether events of verbosity `level` (an `EVENT_LEVEL_*`) should be emitted.
+    pub fn emits(&self, level: u8) -> bool {
+        self.event_level >= level
+    }
+
+    /// Whether a `set_fee_waiver` promotion is running: every fee is skipped until
+    /// `fee_waiver_until`, whatever the configured bps and token fee.
+    pub fn fees_waived(&self) -> Result<bool> {
+        Ok(self.now()? < self.fee_waiver_until)
+    }
+
+    /// Splits `rent` into `(user, referral, treasury)` shares. The referral and
+    /// treasury shares round down and the user gets the exact remainder, so the
+    /// three always sum to `rent`. A `mint_fee` override replaces the global
+    /// `treasury_bps` for that mint. During a fee waiver the user gets all of it.
+    pub fn split_rent(&self, rent: u64, mint_fee: Option<&MintFeeOverride>) -> Result<(u64, u64, u64)> {
+        if self.fees_waived()? {
+            return Ok((rent, 0, 0));
+        }
+        let share = |bps: u16| -> Result<u64> {
+            (rent as u128)
+                .checked_mul(bps as u128)
+                .and_then(|v| v.checked_div(MAX_BPS as u128))
+                .and_then(|v| u64::try_from(v).ok())
+                .ok_or(error!(BurnerError::MathOverflow))
+        };
+        let treasury_bps = mint_fee.map_or(self.treasury_bps, |o| o.fee_bps);
+        require!(
+            self.referral_bps as u32 + treasury_bps as u32 <= MAX_BPS as u32,
+            BurnerError::InvalidBps
+        );
+        let referral = share(self.referral_bps)?;
+        let treasury = share(treasury_bps)?;
+        let user = rent
+            .checked_sub(referral)
+            .and_then(|v| v.checked_sub(treasury))
+            .ok_or(BurnerError::MathOverflow)?;
+        Ok((user, referral, treasury))
+    }
+
+    /// Rejects burning `amount` out of a mint with `supply` if it is more than
+    /// `max_supply_pct_bps` of that supply. Compares by cross-multiplying in
+    /// u128, so exactly hitting the cap is allowed and nothing is rounded.
+    pub fn check_supply_guardrail(&self, amount: u64, supply: u64) -> Result<()> {
+        if self.max_supply_pct_bps == 0 {
+            return Ok(());
+        }
+        let burn_share = (amount as u128)
+            .checked_mul(MAX_BPS as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+        let cap = (supply as u128)
+            .checked_mul(self.max_supply_pct_bps as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+        require!(burn_share <= cap, BurnerError::SupplyGuardrailExceeded);
+        Ok(())
+    }
+}
+
+// Per‑user vault PDA – mainly holds lamports, plus metadata
+#[account]
+#[derive(InitSpace)]
+pub struct VaultAccount {
+    pub owner: Pubkey,           // user controlling withdrawals
+    pub bump: u8,                // PDA bump, lets contexts skip find_program_address
+    pub lamports_collected: u64, // optional stats
+    pub stats_backfilled: bool,  // set once `backfill_vault_stats` has run
+    pub required_cosigner: Option<Pubkey>, // must co-sign withdrawals when set
+    pub rent_floor_snapshot: u64, // rent-exempt minimum captured at creation / refresh_rent_floor
+    pub operator: Option<Pubkey>, // relayer allowed to take an operator fee from reclaimed rent
+    pub compound_threshold: u64, // compound_vault stakes usable lamports once above this, 0 = opted out
+    pub stake_count: u32,        // stake accounts opened by compound_vault, seeds the next one
+    pub strict_mode: bool,       // reject burns of mints that still have a freeze authority
+    pub last_withdraw_at: i64,   // time of the last withdrawal, only read by the withdraw cooldown
+    pub auto_withdraw_threshold: u64, // close_token_account sends usable lamports above this to the owner, 0 = off
+    pub recovery_owner: Option<Pubkey>, // new owner proposed by propose_vault_recovery, None when nothing is pending
+    pub recovery_unlock_at: i64, // finalize_vault_recovery allowed from here, 0 when nothing is pending
+    pub tokens_burned: u128,     // raw tokens burned by the closes that sent rent here
+    pub accounts_closed: u64,    // token accounts closed with their rent sent here
+}
+
+impl VaultAccount {
+    /// A freshly created vault for `owner`: every tally at zero, every opt-in off.
+    /// The one place that lists every field, so all the creating paths stay in step.
+    pub fn init(owner: Pubkey, bump: u8, rent_floor: u64) -> Self {
+        Self {
+            owner,                               // user controlling withdrawals
+            bump,                                // PDA bump
+            lamports_collected:  0,              // optional tally
+            stats_backfilled:    false,          // nothing to restore yet
+            required_cosigner:   None,           // single-sig until set_cosigner
+            rent_floor_snapshot: rent_floor,     // refreshed via refresh_rent_floor
+            operator:            None,           // no fee-taking relayer yet
+            compound_threshold:  0,              // compounding is opt-in
+            stake_count:         0,
+            strict_mode:         false,          // freezable mints allowed until opted in
+            last_withdraw_at:    0,
+            auto_withdraw_threshold: 0,          // rent stays in the vault until withdrawn
+            recovery_owner:      None,           // no support-assisted recovery pending
+            recovery_unlock_at:  0,
+            tokens_burned:       0,
+            accounts_closed:     0,
+        }
+    }
+
+    /// Lamports the vault must keep: the larger of the snapshot and the live minimum.
+    /// The snapshot keeps withdrawals predictable if rent drops mid-operation; taking
+    /// the max means a stale snapshot can never under-reserve if rent rises instead.
+    pub fn rent_floor(&self, data_len: usize) -> Result<u64> {
+        let live_floor = Rent::get()?.minimum_balance(data_len);
+        Ok(self.rent_floor_snapshot.max(live_floor))
+    }
+
+    /// Lamports on the vault above its rent floor, i.e. what a withdrawal can take.
+    pub fn usable_lamports(&self, vault_info: &AccountInfo) -> Result<u64> {
+        let rent_floor = self.rent_floor(vault_info.data_len())?;
+        Ok(vault_info.lamports().saturating_sub(rent_floor))
+    }
+
+    /// Fails with `MissingCosigner` unless `cosigner` signed and is the required one.
+    /// Vaults without a required cosigner always pass.
+    pub fn check_cosigner(&self, cosigner: Option<&Signer>) -> Result<()> {
+        if let Some(required) = self.required_cosigner {
+            require!(
+                cosigner.is_some_and(|signer| signer.key() == required),
+                BurnerError::MissingCosigner
+            );
+        }
+        Ok(())
+    }
+
+    /// Fails with `WithdrawCooldownActive` if the last withdrawal was under
+    /// `cooldown_secs` ago, otherwise stamps `now` as the latest one. Burns never
+    /// touch `last_withdraw_at`, so reclaiming rent doesn't delay a withdrawal.
+    pub fn record_withdrawal(&mut self, cooldown_secs: u32, now: i64) -> Result<()> {
+        if cooldown_secs > 0 && self.last_withdraw_at != 0 {
+            let ready_at = self
+                .last_withdraw_at
+                .checked_add(cooldown_secs as i64)
+                .ok_or(BurnerError::MathOverflow)?;
+            require!(now >= ready_at, BurnerError::WithdrawCooldownActive);
+        }
+        self.last_withdraw_at = now;
+        Ok(())
+    }
+
+    /// Counts `accounts` token accounts closed into this vault, `tokens` raw
+    /// tokens burned along the way. Saturating, like `lamports_collected`.
+    pub fn record_closes(&mut self, accounts: u64, tokens: u64) {
+        self.accounts_closed = self.accounts_closed.saturating_add(accounts);
+        self.tokens_burned   = self.tokens_burned.saturating_add(tokens as u128);
+    }
+}
+
+// Marker PDA for one processed relayed burn – seeds = ["idempotency", user, key].
+// Costs the relayer rent for 8 + 105 bytes (1_677_360 lamports, ~0.00168 SOL) until
+// closed via `close_idempotency_record`.
+#[account]
+#[derive(InitSpace)]
+pub struct IdempotencyRecord {
+    pub user: Pubkey,               // token owner the burn was for
+    pub payer: Pubkey,              // relayer that funded the record, gets the rent back
+    pub idempotency_key: [u8; 32],  // client-chosen key, kept so the PDA can be re-derived
+    pub processed_at: i64,          // Unix timestamp, 0 while unprocessed
+    pub bump: u8,                   // PDA bump
+}
+
+// Per-mint treasury fee override – seeds = ["mint_fee", mint]. Fee-taking
+// instructions always take its address; once initialized its `fee_bps` is used in
+// place of `BurnerState.treasury_bps`.
+#[account]
+#[derive(InitSpace)]
+pub struct MintFeeOverride {
+    pub mint: Pubkey,  // mint this override applies to
+    pub fee_bps: u16,  // treasury share for this mint, in bps
+    pub bump: u8,      // PDA bump
+}
+
+impl MintFeeOverride {
+    /// Reads the override at a context's seeds-pinned `mint_fee` address, `None`
+    /// while nobody has set one and the global `treasury_bps` applies.
+    pub fn load(info: &AccountInfo) -> Result<Option<MintFeeOverride>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Ok(Some(MintFeeOverride::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+}
+
+// Per-shard counter PDA – seeds = ["shard", index]. Burns add to a shard of the
+// client's choosing instead of the singleton state, and `fold_shards` later drains
+// the pending amounts into `BurnerState`. Totals on the state therefore lag by
+// whatever hasn't been folded yet; the sum of state + all shards is always exact.
+#[account]
+#[derive(InitSpace)]
+pub struct ShardCounter {
+    pub index: u8,             // 0..NUM_SHARDS
+    pub pending_burned: u128,  // raw tokens burned since the last fold
+    pub pending_closed: u64,   // token accounts closed since the last fold
+    pub pending_rent: u64,     // rent lamports those closes sent to vaults
+    pub bump: u8,              // PDA bump
+}
+
+impl ShardCounter {
+    /// Adds one burn + close batch to the shard's pending totals.
+    pub fn record(&mut self, burned: u64, closed: u32, rent: u64) -> Result<()> {
+        self.pending_burned = self
+            .pending_burned
+            .checked_add(burned as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+        self.pending_closed = self
+            .pending_closed
+            .checked_add(closed as u64)
+            .ok_or(BurnerError::MathOverflow)?;
+        self.pending_rent = self
+            .pending_rent
+            .checked_add(rent)
+            .ok_or(BurnerError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+// Per-epoch burn rollup – seeds = ["epoch", epoch as u64 LE]. Created lazily by the
+// first burn of each epoch that passes it, which pays the rent for 8 + 33 bytes
+// (~0.0012 SOL); nothing ever closes it, so the rollups stay queryable.
+#[account]
+#[derive(InitSpace)]
+pub struct EpochStats {
+    pub epoch: u64,            // Solana epoch this rollup covers
+    pub burned: u128,          // raw tokens burned during the epoch
+    pub closed: u64,           // token accounts closed during the epoch
+    pub bump: u8,              // PDA bump
+}
+
+impl EpochStats {
+    /// Adds one burn + close batch to the epoch's totals, filling in the key
+    /// fields on the first write.
+    pub fn record(&mut self, epoch: u64, bump: u8, burned: u64, closed: u32) -> Result<()> {
+        self.epoch = epoch;
+        self.bump  = bump;
+        self.burned = self
+            .burned
+            .checked_add(burned as u128)
+            .ok_or(BurnerError::MathOverflow)?;
+        self.closed = self
+            .closed
+            .checked_add(closed as u64)
+            .ok_or(BurnerError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+// Per-owner snapshot of what `preview_batch_net` would count – seeds =
+// ["reclaim_cache", owner]. Written only by `refresh_reclaim_cache`, so UIs can show
+// a total without simulating; `refreshed_at` tells them how old it is. Costs the
+// owner rent for 8 + 61 bytes (~0.0013 SOL).
+#[account]
+#[derive(InitSpace)]
+pub struct ReclaimCache {
+    pub owner: Pubkey,           // wallet the estimate is for
+    pub closable: u32,           // burnable or empty accounts counted at the refresh
+    pub total_reclaimable: u64,  // lamports those accounts held
+    pub refreshed_at: i64,       // BurnerState::now() at the refresh, 0 before the first
+    pub refreshed_slot: u64,     // slot of the refresh, for clients that age by slot
+    pub bump: u8,                // PDA bump
+}
+
+// Time-locked burn commitment – seeds = ["scheduled_burn", depositor, mint,
+// schedule_id LE]. Owns the escrow token account holding the tokens; from
+// `unlock_at` on, anyone can `execute_scheduled_burn`, which burns them and closes
+// both accounts into the depositor's vault; until then the depositor can
+// `cancel_scheduled_burn` instead.
+#[account]
+#[derive(InitSpace)]
+pub struct ScheduledBurn {
+    pub depositor: Pubkey,     // wallet the tokens came from, owner of the vault the rent goes to
+    pub mint: Pubkey,          // mint of the escrowed tokens
+    pub escrow: Pubkey,        // escrow token account, ["scheduled_escrow", this PDA]
+    pub schedule_id: u64,      // depositor-chosen id, so one mint can have several schedules
+    pub amount: u64,           // raw tokens escrowed at scheduling
+    pub created_at: i64,       // BurnerState::now() at scheduling
+    pub unlock_at: i64,        // Unix timestamp from which the burn can be executed
+    pub bump: u8,              // PDA bump
+}
+
+// Per-mint daily burn cap – seeds = ["mint_burn_limit", mint]. Set by
+// `set_mint_daily_cap`; every burn instruction takes its address (per entry in a batch)
+// and books burns of the mint against `daily_cap` over the same rolling window as
+// `daily_burn_limit`.
+#[account]
+#[derive(InitSpace)]
+pub struct MintBurnLimit {
+    pub mint: Pubkey,         // mint the cap applies to
+    pub daily_cap: u64,       // raw tokens per BURN_WINDOW_SECS, 0 = uncapped
+    pub window_start: i64,    // start of the current window
+    pub burned_today: u64,    // raw tokens burned in the current window
+    pub bump: u8,             // PDA bump
+}
+
+impl MintBurnLimit {
+    /// Books `amount` against `daily_cap`, opening a fresh window first if more
+    /// than `BURN_WINDOW_SECS` passed since the last one. Hitting the cap exactly
+    /// is allowed.
+    pub fn record(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now.saturating_sub(self.window_start) > BURN_WINDOW_SECS {
+            self.window_start = now;
+            self.burned_today = 0;
+        }
+        let burned = self
+            .burned_today
+            .checked_add(amount)
+            .ok_or(BurnerError::MathOverflow)?;
+        require!(
+            self.daily_cap == 0 || burned <= self.daily_cap,
+            BurnerError::MintDailyCapExceeded
+        );
+        self.burned_today = burned;
+        Ok(())
+    }
+
+    /// `record` for a context's `mint_burn_limit` address, pinned by its seeds. An
+    /// address nobody has initialized means the mint has no cap.
+    pub fn record_if_set(info: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+        if let Some(mut limit) = Self::load(info)? {
+            limit.record(amount, now)?;
+            limit.save(info)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the limit at `info`, `None` while the address is uninitialized.
+    pub fn load(info: &AccountInfo) -> Result<Option<MintBurnLimit>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Ok(Some(MintBurnLimit::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+
+    /// `load` for an address no seeds constraint pinned, like a batch entry's. Only
+    /// `set_mint_daily_cap` creates these accounts, so an initialized one naming
+    /// `mint` is its PDA; an empty one has to be derived to tell.
+    pub fn load_for_mint(info: &AccountInfo, mint: &Pubkey) -> Result<Option<MintBurnLimit>> {
+        match Self::load(info)? {
+            Some(limit) => {
+                require_keys_eq!(limit.mint, *mint, BurnerError::InvalidMintBurnLimit);
+                Ok(Some(limit))
+            }
+            None => {
+                let (expected, _) = Pubkey::find_program_address(&[MINT_BURN_LIMIT_SEED, mint.as_ref()], &crate::ID);
+                require_keys_eq!(info.key(), expected, BurnerError::InvalidMintBurnLimit);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Writes the limit back to `info`, the account it was loaded from.
+    pub fn save(&self, info: &AccountInfo) -> Result<()> {
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
+
+// One close as kept in a `ReclaimHistory` slot
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, Debug)]
+pub struct ReclaimRecord {
+    pub mint: Pubkey,          // mint of the closed account
+    pub amount: u64,           // raw tokens burned on the way, 0 for an empty account
+    pub rent: u64,             // lamports reclaimed into the vault
+    pub timestamp: i64,        // BurnerState::now() at the close
+}
+
+// Per-owner ring of recent closes – seeds = ["history", owner]. Fixed at
+// RECLAIM_HISTORY_LEN slots so its rent never changes; closes that pass it write
+// to `history_head` and advance it, overwriting the oldest slot once full.
+#[account]
+#[derive(InitSpace)]
+pub struct ReclaimHistory {
+    pub owner: Pubkey,         // wallet whose closes are recorded
+    pub history_head: u16,     // slot the next record goes into
+    pub total_records: u64,    // records ever written; min(total, LEN) slots are filled
+    pub bump: u8,              // PDA bump
+    pub records: [ReclaimRecord; RECLAIM_HISTORY_LEN],
+}
+
+impl ReclaimHistory {
+    /// Writes `record` at the head and advances it, wrapping at capacity.
+    pub fn push(&mut self, record: ReclaimRecord) {
+        let head = self.history_head as usize % RECLAIM_HISTORY_LEN;
+        self.records[head] = record;
+        self.history_head = ((head + 1) % RECLAIM_HISTORY_LEN) as u16;
+        self.total_records = self.total_records.saturating_add(1);
+    }
+}